@@ -0,0 +1,105 @@
+//! `hangul_encode` 처리량 벤치마크
+//!
+//! `cargo bench --no-default-features --bench encode`
+//!
+//! `legacy`와 `bitset`은 같은 문자 집합(예전에 손으로 관리하던 `SPECIAL_CHARS`)을 씁니다.
+//! `legacy`는 예전 `const LazyLock<HashSet<char>>`처럼 문자마다 HashSet을 새로 만들고,
+//! `bitset`은 같은 목록으로 만든 컴파일 타임 [`BmpSet`]을 씁니다.
+//! `current`는 EUC-KR 전용 문자 테이블까지 보는 지금의 `hangul_encode`이므로 집합이 더 크며, 참고용입니다.
+
+use std::collections::HashSet;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use eztrans_sys::codec::{self, BmpSet};
+
+const LINE: &str = "「おはようございます。」♥ 세계@テスト㉷ ①②③ ★ 今日はいい天気ですね。™\n";
+
+/// EUC-KR 전용 문자 테이블을 추가하기 전의 `SPECIAL_CHARS` 목록
+const LEGACY_SPECIAL_CHARS: &[char] = &[
+    '↔', '◁', '◀', '▷', '▶', '♤', '♠', '♡', '♥', '♧', '♣', '⊙', '◈', '▣', '◐', '◑', '▒', '▤', '▥',
+    '▨', '▧', '▦', '▩', '♨', '☏', '☎', '☜', '☞', '↕', '↗', '↙', '↖', '↘', '♩', '♬', '㉿', '㈜',
+    '㏇', '™', '㏂', '㏘', '＂', '＇', '∼', 'ˇ', '˘', '˝', '¡', '˚', '˙', '˛', '¿', 'ː', '∏', '￦',
+    '℉', '€', '㎕', '㎖', '㎗', 'ℓ', '㎘', '㎣', '㎤', '㎥', '㎦', '㎙', '㎚', '㎛', '㎟', '㎠',
+    '㎢', '㏊', '㎍', '㏏', '㎈', '㎉', '㏈', '㎧', '㎨', '㎰', '㎱', '㎲', '㎳', '㎴', '㎵', '㎶',
+    '㎷', '㎸', '㎀', '㎁', '㎂', '㎃', '㎄', '㎺', '㎻', '㎼', '㎽', '㎾', '㎿', '㎐', '㎑', '㎒',
+    '㎓', '㎔', 'Ω', '㏀', '㏁', '㎊', '㎋', '㎌', '㏖', '㏅', '㎭', '㎮', '㎯', '㏛', '㎩', '㎪',
+    '㎫', '㎬', '㏝', '㏐', '㏓', '㏃', '㏉', '㏜', '㏆', '┒', '┑', '┚', '┙', '┖', '┕', '┎', '┍',
+    '┞', '┟', '┡', '┢', '┦', '┧', '┪', '┭', '┮', '┵', '┶', '┹', '┺', '┽', '┾', '╀', '╁', '╃', '╄',
+    '╅', '╆', '╇', '╈', '╉', '╊', '┱', '┲', 'ⅰ', 'ⅱ', 'ⅲ', 'ⅳ', 'ⅴ', 'ⅵ', 'ⅶ', 'ⅷ', 'ⅸ', 'ⅹ', '½',
+    '⅓', '⅔', '¼', '¾', '⅛', '⅜', '⅝', '⅞', 'ⁿ', '₁', '₂', '₃', '₄', 'Ŋ', 'đ', 'Ħ', 'Ĳ', 'Ŀ', 'Ł',
+    'Œ', 'Ŧ', 'ħ', 'ı', 'ĳ', 'ĸ', 'ŀ', 'ł', 'œ', 'ŧ', 'ŋ', 'ŉ', '㉠', '㉡', '㉢', '㉣', '㉤', '㉥',
+    '㉦', '㉧', '㉨', '㉩', '㉪', '㉫', '㉬', '㉭', '㉮', '㉯', '㉰', '㉱', '㉲', '㉳', '㉴', '㉵',
+    '㉶', '㉷', '㉸', '㉹', '㉺', '㉻', '㈀', '㈁', '㈂', '㈃', '㈄', '㈅', '㈆', '㈇', '㈈', '㈉',
+    '㈊', '㈋', '㈌', '㈍', '㈎', '㈏', '㈐', '㈑', '㈒', '㈓', '㈔', '㈕', '㈖', '㈗', '㈘', '㈙',
+    '㈚', '㈛', 'ⓐ', 'ⓑ', 'ⓒ', 'ⓓ', 'ⓔ', 'ⓕ', 'ⓖ', 'ⓗ', 'ⓘ', 'ⓙ', 'ⓚ', 'ⓛ', 'ⓜ', 'ⓝ', 'ⓞ', 'ⓟ',
+    'ⓠ', 'ⓡ', 'ⓢ', 'ⓣ', 'ⓤ', 'ⓥ', 'ⓦ', 'ⓧ', 'ⓨ', 'ⓩ', '①', '②', '③', '④', '⑤', '⑥', '⑦', '⑧', '⑨',
+    '⑩', '⑪', '⑫', '⑬', '⑭', '⑮', '⒜', '⒝', '⒞', '⒟', '⒠', '⒡', '⒢', '⒣', '⒤', '⒥', '⒦', '⒧', '⒨',
+    '⒩', '⒪', '⒫', '⒬', '⒭', '⒮', '⒯', '⒰', '⒱', '⒲', '⒳', '⒴', '⒵', '⑴', '⑵', '⑶', '⑷', '⑸', '⑹',
+    '⑺', '⑻', '⑼', '⑽', '⑾', '⑿', '⒀', '⒁', '⒂',
+];
+
+static LEGACY_BITSET: BmpSet = BmpSet::from_chars(LEGACY_SPECIAL_CHARS);
+
+fn script() -> String {
+    LINE.repeat(8_000)
+}
+
+/// 예전 구현: `needs_encoding`을 부를 때마다 HashSet을 다시 만듭니다.
+fn legacy_encode(input: &str) -> String {
+    codec::encode_with(input, |c| {
+        if c == '@' || c == '\0' || codec::is_hangul_range(c as u32) {
+            Some('x')
+        } else if LEGACY_SPECIAL_CHARS
+            .iter()
+            .cloned()
+            .collect::<HashSet<char>>()
+            .contains(&c)
+        {
+            Some('X')
+        } else {
+            None
+        }
+    })
+}
+
+/// 같은 목록을 비트셋으로 찾는 구현
+fn bitset_encode(input: &str) -> String {
+    codec::encode_with(input, |c| {
+        if c == '@' || c == '\0' || codec::is_hangul_range(c as u32) {
+            Some('x')
+        } else if LEGACY_BITSET.contains(c) {
+            Some('X')
+        } else {
+            None
+        }
+    })
+}
+
+fn measure(name: &str, bytes: usize, mut f: impl FnMut() -> String) {
+    let mut iterations = 0u32;
+    let started = Instant::now();
+    while started.elapsed() < Duration::from_secs(2) || iterations < 3 {
+        black_box(f());
+        iterations += 1;
+    }
+    let per_iter = started.elapsed() / iterations;
+    let mib_per_sec = bytes as f64 / per_iter.as_secs_f64() / (1024.0 * 1024.0);
+    println!("{name:>8}: {per_iter:>12.3?}/iter  {mib_per_sec:>10.2} MiB/s");
+}
+
+fn main() {
+    let input = script();
+    assert_eq!(legacy_encode(&input), bitset_encode(&input));
+
+    println!(
+        "input: {} bytes, {} chars",
+        input.len(),
+        input.chars().count()
+    );
+    measure("legacy", input.len(), || legacy_encode(black_box(&input)));
+    measure("bitset", input.len(), || bitset_encode(black_box(&input)));
+    measure("current", input.len(), || {
+        codec::hangul_encode(black_box(&input))
+    });
+}
@@ -0,0 +1,73 @@
+use std::env;
+use std::path::Path;
+use std::process::ExitCode;
+
+use eztrans_sys::pe::{Machine, PeInfo};
+use eztrans_sys::{DEFAULT_DLL_NAME, DEFAULT_PATH};
+
+/// 설치 폴더(또는 DLL 파일)를 불러오지 않고 검사합니다. Windows가 아니어도 동작합니다.
+///
+/// ```sh
+/// cargo run --no-default-features --bin doctor -- "/mnt/c/Program Files (x86)/ChangShinSoft/ezTrans XP"
+/// ```
+fn main() -> ExitCode {
+    let target = env::args()
+        .nth(1)
+        .unwrap_or_else(|| DEFAULT_PATH.to_string());
+    let path = Path::new(&target);
+    let dll_path = if path.is_dir() {
+        path.join(DEFAULT_DLL_NAME)
+    } else {
+        path.to_path_buf()
+    };
+
+    let info = match PeInfo::read(&dll_path) {
+        Ok(info) => info,
+        Err(e) => {
+            eprintln!("{}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut problems = Vec::new();
+    println!("file:    {}", info.path);
+    println!("machine: {}", info.machine);
+    match info.file_version {
+        Some(version) => println!("version: {}", version),
+        None => println!("version: (no version resource)"),
+    }
+    if !info.is_dll {
+        problems.push("not a DLL".to_string());
+    }
+    if info.machine != Machine::I386 {
+        problems.push(format!(
+            "ezTrans XP engine must be 32-bit, but this file is {}",
+            info.machine
+        ));
+    }
+
+    let capabilities = info.capabilities();
+    print!("{}", capabilities);
+
+    let has = |name| capabilities.has_export(name);
+    if !has("J2K_InitializeEx") && !has("J2K_Initialize") {
+        problems.push("no initializer (J2K_InitializeEx or J2K_Initialize)".to_string());
+    }
+    if !has("J2K_TranslateMMNT") && !has("J2K_TranslateMMNTW") {
+        problems
+            .push("no translate function (J2K_TranslateMMNT or J2K_TranslateMMNTW)".to_string());
+    }
+    if !has("J2K_FreeMem") {
+        problems.push("J2K_FreeMem is missing".to_string());
+    }
+
+    if problems.is_empty() {
+        println!("OK");
+        ExitCode::SUCCESS
+    } else {
+        for problem in &problems {
+            println!("problem: {}", problem);
+        }
+        ExitCode::FAILURE
+    }
+}
@@ -0,0 +1,33 @@
+//! `src/special_chars_table.rs`를 다시 만듭니다.
+//!
+//! `cargo run --no-default-features --bin gen_special_chars > src/special_chars_table.rs`
+
+use eztrans_sys::codec;
+
+fn main() {
+    let chars = codec::euc_kr_only_chars();
+
+    println!(
+        "// @generated by `cargo run --no-default-features --bin gen_special_chars`. Do not edit."
+    );
+    println!("//");
+    println!(
+        "// EUC-KR로는 표현되지만 Shift-JIS로는 표현되지 않는 문자 (한글 제외, {}자).",
+        chars.len()
+    );
+    println!(
+        "// `J2K_TranslateMMNT`의 Shift-JIS 입력 -> EUC-KR 출력 경로를 그대로 통과할 수 없습니다."
+    );
+    println!();
+    println!("use crate::codec::BmpSet;");
+    println!();
+    println!("pub(crate) static EUC_KR_ONLY: BmpSet = BmpSet::from_chars(&[");
+    for line in chars.chunks(8) {
+        let line: Vec<String> = line
+            .iter()
+            .map(|&c| format!("'\\u{{{:04X}}}'", c as u32))
+            .collect();
+        println!("    {},", line.join(", "));
+    }
+    println!("]);");
+}
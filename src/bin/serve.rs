@@ -0,0 +1,96 @@
+use std::env;
+use std::time::Duration;
+
+use eztrans_sys::server::{EngineThread, Server, ServerConfig};
+use eztrans_sys::{EzTransConfig, EzTransError, EzTransLib, MockEngine, TranslationEngine};
+
+/// `EzTransLib`을 HTTP로 엽니다.
+///
+/// ```sh
+/// serve --bind=127.0.0.1:8080 --config=ezTrans.toml --allow_shutdown
+/// curl -d '{"text":"おはようございます。"}' http://127.0.0.1:8080/translate
+/// curl -X POST http://127.0.0.1:8080/shutdown
+/// ```
+///
+/// `--allow_shutdown`을 주면 `/shutdown`으로 끌 수 있고, 이때 남은 요청에 답한 뒤 엔진을 종료(`J2K_Terminate`)합니다.
+fn main() -> Result<(), EzTransError> {
+    let mut config = ServerConfig::default();
+    let mut folder_path: Option<String> = None;
+    let mut config_path: Option<String> = None;
+    let mut use_mock = false;
+
+    for arg in env::args().skip(1) {
+        if let Some(bind) = arg.strip_prefix("--bind=") {
+            config.bind = bind.to_string();
+        } else if let Some(path) = arg.strip_prefix("--folder_path=") {
+            folder_path = Some(path.trim_matches('"').to_string());
+        } else if let Some(path) = arg.strip_prefix("--config=") {
+            config_path = Some(path.trim_matches('"').to_string());
+        } else if arg == "--mock" {
+            use_mock = true;
+        } else if arg == "--allow_shutdown" {
+            config.allow_shutdown = true;
+        } else if let Some(value) = arg.strip_prefix("--timeout_ms=") {
+            config.timeout = Some(Duration::from_millis(parse("--timeout_ms", value)?));
+        } else if let Some(value) = arg.strip_prefix("--max_body_bytes=") {
+            config.max_body_bytes = parse("--max_body_bytes", value)?;
+        } else if let Some(value) = arg.strip_prefix("--max_batch=") {
+            config.max_batch = parse("--max_batch", value)?;
+        } else if let Some(value) = arg.strip_prefix("--workers=") {
+            config.workers = parse("--workers", value)?;
+        }
+    }
+
+    if use_mock {
+        let engine = EngineThread::spawn(
+            || {
+                let engine = MockEngine::new();
+                engine.initialize(None, None)?;
+                Ok(engine)
+            },
+            |engine| {
+                let _ = engine.terminate();
+            },
+        )?;
+        run(config, engine)
+    } else {
+        let mut ez_config = match config_path {
+            Some(path) => EzTransConfig::from_file(path)?,
+            None => EzTransConfig::default(),
+        };
+        if let Some(folder_path) = folder_path {
+            ez_config.install_path = folder_path;
+        }
+        let engine = EngineThread::spawn(
+            move || EzTransLib::with_config(ez_config)?.initialize(None, None),
+            |engine| {
+                if let Err(e) = engine.terminate() {
+                    eprintln!("Failed to terminate: {}", e);
+                }
+            },
+        )?;
+        run(config, engine)
+    }
+}
+
+fn parse<T: std::str::FromStr>(name: &str, value: &str) -> Result<T, EzTransError>
+where
+    T::Err: std::fmt::Display,
+{
+    value
+        .parse()
+        .map_err(|e| EzTransError::ConfigError(format!("{}: {}", name, e)))
+}
+
+fn run<E: TranslationEngine + 'static>(
+    config: ServerConfig,
+    engine: EngineThread<E>,
+) -> Result<(), EzTransError> {
+    let server = Server::bind(config, engine)?;
+    if let Some(addr) = server.local_addr() {
+        eprintln!("Listening on http://{}", addr);
+    }
+    server.run();
+    // `server`가 drop되면서 엔진 스레드가 엔진을 종료합니다.
+    Ok(())
+}
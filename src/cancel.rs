@@ -0,0 +1,254 @@
+use std::cell::Cell;
+use std::fmt;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Condvar, Mutex, PoisonError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use crate::{EzTransError, TransErr};
+
+/// 감시 스레드가 토큰의 취소 여부를 확인하는 간격
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// 다른 스레드에서 진행 중인 번역을 취소할 때 사용합니다.
+///
+/// 복제한 토큰은 같은 상태를 공유합니다. 한 번 취소한 토큰은 되돌릴 수 없습니다.
+/// 다른 스레드에는 복제해서 넘깁니다. (`Sync`가 아니므로 참조는 넘길 수 없습니다.)
+/// 그래서 복제본이 없는 토큰은 번역 중에 취소될 수 없고, [`run_cancellable`]은 이런 토큰을 감시하지 않습니다.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken {
+    cancelled: Arc<AtomicBool>,
+    not_sync: PhantomData<Cell<()>>,
+}
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Release);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Acquire)
+    }
+
+    /// 다른 곳에 복제본이 있어서 번역 중에 취소될 수 있는지 여부
+    fn has_clones(&self) -> bool {
+        Arc::strong_count(&self.cancelled) > 1
+    }
+}
+
+/// 다른 스레드에서 엔진에 번역을 멈추라고 요청하는 핸들 (`J2K_StopTranslation`)
+#[derive(Clone)]
+pub struct StopHandle(Arc<dyn Fn() + Send + Sync>);
+
+impl StopHandle {
+    pub fn new(stop: impl Fn() + Send + Sync + 'static) -> Self {
+        StopHandle(Arc::new(stop))
+    }
+
+    pub fn stop(&self) {
+        (self.0)()
+    }
+}
+
+impl fmt::Debug for StopHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("StopHandle").finish_non_exhaustive()
+    }
+}
+
+/// 감시 스레드와 번역하는 스레드가 공유하는 상태
+#[derive(Default)]
+struct Watch {
+    /// `translate`가 끝났는지 여부
+    done: bool,
+    /// 감시 스레드가 멈추라고 요청한 이유
+    stopped: Option<TransErr>,
+}
+
+/// `translate`를 실행하는 동안 감시 스레드를 띄워, `cancel`이 취소되거나 `timeout`이 지나면 `stop`을 한 번 호출합니다.
+///
+/// 멈추라는 요청을 받은 번역이 실패하면 [`TransErr::Cancelled`]나 [`TransErr::TimedOut`]을 반환하고,
+/// 그래도 번역이 끝났다면 그 결과를 그대로 반환합니다.
+/// `stop`이 없거나, `timeout`이 없고 `cancel`의 복제본도 없어서 멈출 일이 없으면 감시 스레드 없이 `translate`만 실행합니다.
+///
+/// 요청은 번역이 끝났다고 기록되기 전에만 보내지만, 엔진 함수가 막 돌아온 뒤에 도착할 수는 있습니다.
+pub fn run_cancellable<T>(
+    stop: Option<StopHandle>,
+    cancel: &CancellationToken,
+    timeout: Option<Duration>,
+    translate: impl FnOnce() -> Result<T, EzTransError>,
+) -> Result<T, EzTransError> {
+    if cancel.is_cancelled() {
+        return Err(EzTransError::TranslationError(TransErr::Cancelled));
+    }
+    let cancellable = cancel.has_clones();
+    let stop = match stop {
+        Some(stop) if timeout.is_some() || cancellable => stop,
+        _ => return translate(),
+    };
+
+    let deadline = timeout.map(|timeout| Instant::now() + timeout);
+    let watch = Arc::new((Mutex::new(Watch::default()), Condvar::new()));
+    let watchdog = {
+        let watch = watch.clone();
+        let cancel = cancel.clone();
+        thread::spawn(move || {
+            let (lock, cvar) = &*watch;
+            let mut state = lock.lock().unwrap_or_else(PoisonError::into_inner);
+            while !state.done {
+                let now = Instant::now();
+                let reason = if cancel.is_cancelled() {
+                    Some(TransErr::Cancelled)
+                } else if deadline.is_some_and(|deadline| now >= deadline) {
+                    Some(TransErr::TimedOut)
+                } else {
+                    None
+                };
+                if let Some(reason) = reason {
+                    // 잠금을 쥔 채로 보내므로 번역이 끝났다고 기록된 뒤에는 보내지 않습니다.
+                    stop.stop();
+                    state.stopped = Some(reason);
+                    return;
+                }
+                let until_deadline = deadline.map(|deadline| deadline - now);
+                let wait = match until_deadline {
+                    Some(wait) if cancellable => wait.min(POLL_INTERVAL),
+                    Some(wait) => wait,
+                    None => POLL_INTERVAL,
+                };
+                state = cvar
+                    .wait_timeout(state, wait)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0;
+            }
+        })
+    };
+
+    let result = translate();
+
+    let stopped = {
+        let (lock, cvar) = &*watch;
+        let mut state = lock.lock().unwrap_or_else(PoisonError::into_inner);
+        state.done = true;
+        cvar.notify_one();
+        state.stopped.take()
+    };
+    let _ = watchdog.join();
+    match (result, stopped) {
+        (Err(_), Some(reason)) => Err(EzTransError::TranslationError(reason)),
+        (result, _) => result,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test]
+    fn test_already_cancelled_does_not_run() {
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+        let result = run_cancellable(None, &cancel, None, || -> Result<(), EzTransError> {
+            panic!("should not run")
+        });
+        assert!(matches!(
+            result,
+            Err(EzTransError::TranslationError(TransErr::Cancelled))
+        ));
+    }
+
+    #[test]
+    fn test_finished_in_time_returns_result() {
+        let result = run_cancellable(
+            None,
+            &CancellationToken::new(),
+            Some(Duration::from_secs(10)),
+            || Ok(1),
+        );
+        assert_eq!(result.unwrap(), 1);
+    }
+
+    fn counting_stop() -> (StopHandle, Arc<AtomicUsize>) {
+        let count = Arc::new(AtomicUsize::new(0));
+        let stops = count.clone();
+        let handle = StopHandle::new(move || {
+            stops.fetch_add(1, Ordering::SeqCst);
+        });
+        (handle, count)
+    }
+
+    #[test]
+    fn test_finished_translation_wins_over_timeout() {
+        let (stop, count) = counting_stop();
+        let result = run_cancellable(
+            Some(stop),
+            &CancellationToken::new(),
+            Some(Duration::from_millis(10)),
+            || {
+                thread::sleep(Duration::from_millis(50));
+                Ok(1)
+            },
+        );
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_stopped_translation_reports_reason_and_stops_once() {
+        let (stop, count) = counting_stop();
+        let result: Result<(), _> = run_cancellable(
+            Some(stop),
+            &CancellationToken::new(),
+            Some(Duration::from_millis(10)),
+            || {
+                thread::sleep(Duration::from_millis(100));
+                Err(EzTransError::EngineCallFailed {
+                    function: "test",
+                    code: -1,
+                })
+            },
+        );
+        assert!(matches!(
+            result,
+            Err(EzTransError::TranslationError(TransErr::TimedOut))
+        ));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_cancel_from_clone_stops_translation() {
+        let (stop, count) = counting_stop();
+        let cancel = CancellationToken::new();
+        let canceller = cancel.clone();
+        let result: Result<(), _> = run_cancellable(Some(stop), &cancel, None, || {
+            canceller.cancel();
+            thread::sleep(Duration::from_millis(50));
+            Err(EzTransError::EngineCallFailed {
+                function: "test",
+                code: -1,
+            })
+        });
+        assert!(matches!(
+            result,
+            Err(EzTransError::TranslationError(TransErr::Cancelled))
+        ));
+        assert_eq!(count.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_nothing_to_watch_never_stops() {
+        let (stop, count) = counting_stop();
+        let result = run_cancellable(Some(stop), &CancellationToken::new(), None, || {
+            thread::sleep(Duration::from_millis(20));
+            Ok(())
+        });
+        assert!(result.is_ok());
+        assert_eq!(count.load(Ordering::SeqCst), 0);
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+
+use crate::TranslateMode;
+
+/// `J2KEngine.dll`이 내보낼 수 있는 함수, 그 함수를 바인딩하는 Cargo 기능 이름, 이 빌드에서 그 기능이 켜져 있는지 여부
+pub const J2K_EXPORTS: [(&str, &str, bool); 20] = [
+    ("J2K_FreeMem", "free-mem", cfg!(feature = "free-mem")),
+    (
+        "J2K_GetPriorDict",
+        "get-prior-dict",
+        cfg!(feature = "get-prior-dict"),
+    ),
+    (
+        "J2K_GetProperty",
+        "get-property",
+        cfg!(feature = "get-property"),
+    ),
+    ("J2K_Initialize", "initialize", cfg!(feature = "initialize")),
+    (
+        "J2K_InitializeEx",
+        "initialize-ex",
+        cfg!(feature = "initialize-ex"),
+    ),
+    (
+        "J2K_ReloadUserDict",
+        "reload-user-dict",
+        cfg!(feature = "reload-user-dict"),
+    ),
+    (
+        "J2K_SetDelJPN",
+        "set-del-jpn",
+        cfg!(feature = "set-del-jpn"),
+    ),
+    ("J2K_SetField", "set-field", cfg!(feature = "set-field")),
+    (
+        "J2K_SetHnj2han",
+        "set-hnj2han",
+        cfg!(feature = "set-hnj2han"),
+    ),
+    ("J2K_SetJWin", "set-jwin", cfg!(feature = "set-jwin")),
+    (
+        "J2K_SetPriorDict",
+        "set-prior-dict",
+        cfg!(feature = "set-prior-dict"),
+    ),
+    (
+        "J2K_SetProperty",
+        "set-property",
+        cfg!(feature = "set-property"),
+    ),
+    (
+        "J2K_StopTranslation",
+        "stop-translation",
+        cfg!(feature = "stop-translation"),
+    ),
+    ("J2K_Terminate", "terminate", cfg!(feature = "terminate")),
+    (
+        "J2K_TranslateChat",
+        "translate-chat",
+        cfg!(feature = "translate-chat"),
+    ),
+    (
+        "J2K_TranslateFM",
+        "translate-fm",
+        cfg!(feature = "translate-fm"),
+    ),
+    (
+        "J2K_TranslateMM",
+        "translate-mm",
+        cfg!(feature = "translate-mm"),
+    ),
+    (
+        "J2K_TranslateMMEx",
+        "translate-mmex",
+        cfg!(feature = "translate-mmex"),
+    ),
+    (
+        "J2K_TranslateMMNT",
+        "translate-mmnt",
+        cfg!(feature = "translate-mmnt"),
+    ),
+    (
+        "J2K_TranslateMMNTW",
+        "translate-mmntw",
+        cfg!(feature = "translate-mmntw"),
+    ),
+];
+
+/// Ehnd가 추가하는 UTF-16 번역 함수
+pub const EHND_EXPORT: &str = "J2K_TranslateMMNTW";
+
+/// 이 빌드에 켜져 있는 함수 바인딩 기능 목록 ([`J2K_EXPORTS`] 순서)
+pub fn compiled_features() -> Vec<&'static str> {
+    J2K_EXPORTS
+        .iter()
+        .filter(|(_, _, compiled)| *compiled)
+        .map(|(_, feature, _)| *feature)
+        .collect()
+}
+
+/// 불러온 DLL이 실제로 내보내는 함수와 이 빌드에서 쓸 수 있는 함수를 정리한 보고서
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Capabilities {
+    /// 검사한 DLL의 경로
+    pub dll_path: String,
+    /// DLL에서 찾은 `J2K_*` 함수 ([`J2K_EXPORTS`] 순서)
+    pub exports: Vec<&'static str>,
+    /// Ehnd의 `J2K_TranslateMMNTW`가 있는지 여부
+    pub ehnd: bool,
+    /// 이 빌드에 켜져 있는 함수 바인딩 기능
+    pub compiled_features: Vec<&'static str>,
+}
+
+impl Capabilities {
+    /// `has_export`가 참인 함수들로 보고서를 만듭니다.
+    pub fn probe(dll_path: &str, mut has_export: impl FnMut(&str) -> bool) -> Self {
+        let exports: Vec<&'static str> = J2K_EXPORTS
+            .iter()
+            .map(|(name, _, _)| *name)
+            .filter(|name| has_export(name))
+            .collect();
+        Capabilities {
+            dll_path: dll_path.to_string(),
+            ehnd: exports.contains(&EHND_EXPORT),
+            exports,
+            compiled_features: compiled_features(),
+        }
+    }
+
+    pub fn has_export(&self, name: &str) -> bool {
+        self.exports.contains(&name)
+    }
+
+    /// DLL에 없는 `J2K_*` 함수
+    pub fn missing_exports(&self) -> Vec<&'static str> {
+        J2K_EXPORTS
+            .iter()
+            .map(|(name, _, _)| *name)
+            .filter(|name| !self.has_export(name))
+            .collect()
+    }
+
+    /// DLL에 있고 이 빌드에서 바인딩한 함수인지 확인합니다.
+    pub fn is_usable(&self, name: &str) -> bool {
+        J2K_EXPORTS
+            .iter()
+            .find(|(export, _, _)| *export == name)
+            .is_some_and(|(_, feature, _)| {
+                self.has_export(name) && self.compiled_features.contains(feature)
+            })
+    }
+
+    /// DLL에는 있지만 기능을 켜지 않아 쓸 수 없는 함수와 그 기능 이름
+    pub fn disabled_exports(&self) -> Vec<(&'static str, &'static str)> {
+        J2K_EXPORTS
+            .iter()
+            .filter(|(name, feature, _)| {
+                self.has_export(name) && !self.compiled_features.contains(feature)
+            })
+            .map(|(name, feature, _)| (*name, *feature))
+            .collect()
+    }
+
+    /// 사용할 수 있는 번역 모드 ([`TranslateMode::ALL`] 순서)
+    pub fn translate_modes(&self) -> Vec<TranslateMode> {
+        TranslateMode::ALL
+            .into_iter()
+            .filter(|mode| match mode {
+                TranslateMode::Auto => {
+                    self.is_usable("J2K_TranslateMMNT") || self.is_usable(EHND_EXPORT)
+                }
+                TranslateMode::Chat => self.is_usable("J2K_TranslateChat"),
+                TranslateMode::Fm => self.is_usable("J2K_TranslateFM"),
+                TranslateMode::Mm => self.is_usable("J2K_TranslateMM"),
+                TranslateMode::MmEx => self.is_usable("J2K_TranslateMMEx"),
+                TranslateMode::Mmnt => self.is_usable("J2K_TranslateMMNT"),
+                TranslateMode::Mmntw => self.is_usable(EHND_EXPORT),
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for Capabilities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.dll_path)?;
+        writeln!(f, "  Ehnd: {}", if self.ehnd { "yes" } else { "no" })?;
+        for (name, feature, _) in J2K_EXPORTS {
+            let status = match (
+                self.has_export(name),
+                self.compiled_features.contains(&feature),
+            ) {
+                (true, true) => "ok".to_string(),
+                (true, false) => format!("exported, enable feature `{}`", feature),
+                (false, _) => "missing".to_string(),
+            };
+            writeln!(f, "  {:<20} {}", name, status)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_probe() {
+        let capabilities = Capabilities::probe("J2KEngine.dll", |name| {
+            matches!(name, "J2K_TranslateMMNT" | "J2K_TranslateChat")
+        });
+        assert_eq!(
+            capabilities.exports,
+            ["J2K_TranslateChat", "J2K_TranslateMMNT"]
+        );
+        assert!(!capabilities.ehnd);
+        assert_eq!(capabilities.missing_exports().len(), 18);
+        assert_eq!(
+            capabilities.is_usable("J2K_TranslateChat"),
+            cfg!(feature = "translate-chat")
+        );
+        assert_eq!(
+            capabilities
+                .translate_modes()
+                .contains(&TranslateMode::Mmnt),
+            cfg!(feature = "translate-mmnt")
+        );
+        assert!(!capabilities
+            .translate_modes()
+            .contains(&TranslateMode::Mmntw));
+    }
+}
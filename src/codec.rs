@@ -0,0 +1,404 @@
+use std::fmt::Write;
+
+use crate::special_chars_table::EUC_KR_ONLY;
+
+/// BMP(U+0000..=U+FFFF) 문자 집합을 나타내는 비트셋입니다.
+///
+/// 컴파일 타임에 만들어지므로 초기화 비용이 없고, 조회할 때 할당이나 해시 계산이 필요 없습니다.
+pub struct BmpSet([u64; 1024]);
+
+impl BmpSet {
+    /// 문자 목록으로 집합을 만듭니다. BMP 밖의 문자가 있으면 컴파일 에러가 납니다.
+    pub const fn from_chars(chars: &[char]) -> Self {
+        let mut bits = [0u64; 1024];
+        let mut i = 0;
+        while i < chars.len() {
+            let code = chars[i] as u32;
+            assert!(code <= 0xFFFF, "BmpSet only holds BMP characters");
+            bits[(code >> 6) as usize] |= 1 << (code & 63);
+            i += 1;
+        }
+        Self(bits)
+    }
+
+    pub const fn contains(&self, c: char) -> bool {
+        let code = c as u32;
+        code <= 0xFFFF && self.0[(code >> 6) as usize] & (1 << (code & 63)) != 0
+    }
+}
+
+/// 엔진이 망가뜨리는 것으로 확인된 문자 목록 (손으로 관리)
+static SPECIAL_CHARS: BmpSet = BmpSet::from_chars(&[
+    '↔', '◁', '◀', '▷', '▶', '♤', '♠', '♡', '♥', '♧', '♣', '⊙', '◈', '▣', '◐', '◑', '▒', '▤', '▥',
+    '▨', '▧', '▦', '▩', '♨', '☏', '☎', '☜', '☞', '↕', '↗', '↙', '↖', '↘', '♩', '♬', '㉿', '㈜',
+    '㏇', '™', '㏂', '㏘', '＂', '＇', '∼', 'ˇ', '˘', '˝', '¡', '˚', '˙', '˛', '¿', 'ː', '∏', '￦',
+    '℉', '€', '㎕', '㎖', '㎗', 'ℓ', '㎘', '㎣', '㎤', '㎥', '㎦', '㎙', '㎚', '㎛', '㎟', '㎠',
+    '㎢', '㏊', '㎍', '㏏', '㎈', '㎉', '㏈', '㎧', '㎨', '㎰', '㎱', '㎲', '㎳', '㎴', '㎵', '㎶',
+    '㎷', '㎸', '㎀', '㎁', '㎂', '㎃', '㎄', '㎺', '㎻', '㎼', '㎽', '㎾', '㎿', '㎐', '㎑', '㎒',
+    '㎓', '㎔', 'Ω', '㏀', '㏁', '㎊', '㎋', '㎌', '㏖', '㏅', '㎭', '㎮', '㎯', '㏛', '㎩', '㎪',
+    '㎫', '㎬', '㏝', '㏐', '㏓', '㏃', '㏉', '㏜', '㏆', '┒', '┑', '┚', '┙', '┖', '┕', '┎', '┍',
+    '┞', '┟', '┡', '┢', '┦', '┧', '┪', '┭', '┮', '┵', '┶', '┹', '┺', '┽', '┾', '╀', '╁', '╃', '╄',
+    '╅', '╆', '╇', '╈', '╉', '╊', '┱', '┲', 'ⅰ', 'ⅱ', 'ⅲ', 'ⅳ', 'ⅴ', 'ⅵ', 'ⅶ', 'ⅷ', 'ⅸ', 'ⅹ', '½',
+    '⅓', '⅔', '¼', '¾', '⅛', '⅜', '⅝', '⅞', 'ⁿ', '₁', '₂', '₃', '₄', 'Ŋ', 'đ', 'Ħ', 'Ĳ', 'Ŀ', 'Ł',
+    'Œ', 'Ŧ', 'ħ', 'ı', 'ĳ', 'ĸ', 'ŀ', 'ł', 'œ', 'ŧ', 'ŋ', 'ŉ', '㉠', '㉡', '㉢', '㉣', '㉤', '㉥',
+    '㉦', '㉧', '㉨', '㉩', '㉪', '㉫', '㉬', '㉭', '㉮', '㉯', '㉰', '㉱', '㉲', '㉳', '㉴', '㉵',
+    '㉶', '㉷', '㉸', '㉹', '㉺', '㉻', '㈀', '㈁', '㈂', '㈃', '㈄', '㈅', '㈆', '㈇', '㈈', '㈉',
+    '㈊', '㈋', '㈌', '㈍', '㈎', '㈏', '㈐', '㈑', '㈒', '㈓', '㈔', '㈕', '㈖', '㈗', '㈘', '㈙',
+    '㈚', '㈛', 'ⓐ', 'ⓑ', 'ⓒ', 'ⓓ', 'ⓔ', 'ⓕ', 'ⓖ', 'ⓗ', 'ⓘ', 'ⓙ', 'ⓚ', 'ⓛ', 'ⓜ', 'ⓝ', 'ⓞ', 'ⓟ',
+    'ⓠ', 'ⓡ', 'ⓢ', 'ⓣ', 'ⓤ', 'ⓥ', 'ⓦ', 'ⓧ', 'ⓨ', 'ⓩ', '①', '②', '③', '④', '⑤', '⑥', '⑦', '⑧', '⑨',
+    '⑩', '⑪', '⑫', '⑬', '⑭', '⑮', '⒜', '⒝', '⒞', '⒟', '⒠', '⒡', '⒢', '⒣', '⒤', '⒥', '⒦', '⒧', '⒨',
+    '⒩', '⒪', '⒫', '⒬', '⒭', '⒮', '⒯', '⒰', '⒱', '⒲', '⒳', '⒴', '⒵', '⑴', '⑵', '⑶', '⑷', '⑸', '⑹',
+    '⑺', '⑻', '⑼', '⑽', '⑾', '⑿', '⒀', '⒁', '⒂',
+]);
+
+/// 한글 문자나 특수 문자는 "+x" 또는 "+X" 접두사와 함께 16진수 유니코드 값으로 변환됩니다. 그 외의 문자는 그대로 유지됩니다.
+/// 왜 필요? 이지트랜스 엔진은 한글 문자나 특수 문자를 처리할 때 문제가 발생할 수 있습니다. 이를 방지하기 위해 문자를 변환한다고 추측하고 있습니다.
+///
+/// 입력에 원래부터 `+x`/`+X`가 들어 있으면 그 `+`도 `+x002B`로 이스케이프하므로,
+/// [`hangul_decode`]는 어떤 입력에 대해서도 이 함수의 정확한 역함수입니다.
+pub fn hangul_encode(input: &str) -> String {
+    encode_with(input, hangul_marker)
+}
+
+/// [`hangul_encode`]가 문자 `c`에 쓰는 이스케이프 접두사. 이스케이프하지 않는 문자는 `None`입니다.
+pub fn hangul_marker(c: char) -> Option<char> {
+    if c == '@' || c == '\0' || is_hangul_range(c as u32) {
+        Some('x')
+    } else if needs_encoding(c) {
+        Some('X')
+    } else {
+        None
+    }
+}
+
+/// `marker_for`가 `Some(marker)`를 돌려주는 문자를 `+{marker}NNNN`으로 이스케이프합니다.
+///
+/// [`hangul_encode`]와 마찬가지로 원문의 `+x`/`+X`도 이스케이프하므로 [`hangul_decode`]로 정확히 되돌릴 수 있습니다.
+pub fn encode_with(input: &str, mut marker_for: impl FnMut(char) -> Option<char>) -> String {
+    let mut output = String::with_capacity(input.len() * 2);
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        // 원문의 "+x"/"+X"는 '+'를 이스케이프해서 디코딩할 때 이스케이프로 오인되지 않게 합니다.
+        if c == '+' && matches!(chars.peek(), Some('x') | Some('X')) {
+            write_escape(&mut output, 'x', c);
+        } else if let Some(marker) = marker_for(c) {
+            write_escape(&mut output, marker, c);
+        } else {
+            output.push(c);
+        }
+    }
+    output
+}
+
+/// 문자 하나를 `+{marker}NNNN` 형식으로 씁니다.
+///
+/// 이스케이프는 항상 16진수 4자리입니다. U+FFFF보다 큰 문자는 UTF-16 서로게이트 쌍으로 나눠
+/// 이스케이프 두 개(`+xD83D+xDE00`)로 쓰므로, 모든 유니코드 스칼라 값을 손실 없이 표현할 수 있습니다.
+pub fn write_escape(output: &mut String, marker: char, c: char) {
+    let mut units = [0u16; 2];
+    for unit in c.encode_utf16(&mut units) {
+        write!(output, "+{}{:04X}", marker, unit).unwrap();
+    }
+}
+
+/// 문자열에 인코딩이 필요한 문자가 하나라도 있는지 확인합니다.
+pub fn contains_encodable(input: &str) -> bool {
+    input.chars().any(is_protected)
+}
+
+/// 번역 전에 보호해야 하는 문자(`@`, NUL, 한글, 특수 문자)인지 확인합니다.
+pub fn is_protected(c: char) -> bool {
+    c == '@' || c == '\0' || is_hangul_range(c as u32) || needs_encoding(c)
+}
+
+/// Shift-JIS로 표현할 수 있는 문자인지 확인합니다.
+pub fn is_sjis_mappable(c: char) -> bool {
+    let mut buf = [0u8; 4];
+    let (_, _, had_errors) = encoding_rs::SHIFT_JIS.encode(c.encode_utf8(&mut buf));
+    !had_errors
+}
+
+/// Shift-JIS로 표현할 수 없는 문자를 바이트 오프셋과 함께 모두 찾습니다.
+pub fn sjis_unmappable(input: &str) -> Vec<(usize, char)> {
+    input
+        .char_indices()
+        .filter(|&(_, c)| !is_sjis_mappable(c))
+        .collect()
+}
+
+/// 한글 문자를 판별합니다.
+pub const fn is_hangul_range(code: u32) -> bool {
+    // Hangul Jamo //한글 자모
+    (code >= 0x1100 && code <= 0x11FF)
+    // Hangul Compatibility Jamo //한글 호환 자모
+    || (code >= 0x3130 && code <= 0x318F)
+    // Hangul Jamo Extended-A //한글 자모 확장-A
+    || (code >= 0xA960 && code <= 0xA97F)
+    // Hangul Syllables //한글 음절
+    || (code >= 0xAC00 && code <= 0xD7A3)
+    // Hangul Jamo Extended-B //한글 자모 확장-B
+    || (code >= 0xD7B0 && code <= 0xD7FF)
+}
+
+/// 이지트랜스 엔진이 처리할 수 없는 문자인지 확인합니다.
+///
+/// 손으로 모은 `SPECIAL_CHARS`와, 코드페이지에서 계산한 [`euc_kr_only_chars`] 테이블을 함께 봅니다.
+pub fn needs_encoding(c: char) -> bool {
+    SPECIAL_CHARS.contains(c) || EUC_KR_ONLY.contains(c)
+}
+
+/// EUC-KR로는 표현되지만 Shift-JIS로는 표현되지 않는 BMP 문자를 계산합니다. 한글은 제외합니다.
+///
+/// 이런 문자는 `J2K_TranslateMMNT`의 Shift-JIS 입력 -> EUC-KR 출력 경로를 그대로 통과할 수 없습니다.
+/// 커밋된 `special_chars_table.rs`는 `gen_special_chars` 바이너리로 이 함수의 결과를 저장한 것입니다.
+pub fn euc_kr_only_chars() -> Vec<char> {
+    let encodes = |encoding: &'static encoding_rs::Encoding, c: char| {
+        let mut buf = [0u8; 4];
+        !encoding.encode(c.encode_utf8(&mut buf)).2
+    };
+    (0..=0xFFFF)
+        .filter_map(char::from_u32)
+        .filter(|&c| !is_hangul_range(c as u32))
+        .filter(|&c| encodes(encoding_rs::EUC_KR, c) && !encodes(encoding_rs::SHIFT_JIS, c))
+        .collect()
+}
+
+/// "+x" 또는 "+X" 접두사와 함께 16진수 유니코드 값으로 변환된 문자를 원래 문자로 변환합니다.
+///
+/// 서로게이트 쌍을 이루는 두 이스케이프는 하나의 문자로 합칩니다. 올바른 이스케이프가 아닌 부분(짝이 없는
+/// 서로게이트 포함)은 입력 그대로 남겨 둡니다.
+pub fn hangul_decode(input: &str) -> String {
+    decode_with(input, |_| {})
+}
+
+/// [`hangul_decode`]와 같지만, 이스케이프에서 복원한 문자마다 `on_decoded`를 호출합니다.
+pub fn decode_with(input: &str, mut on_decoded: impl FnMut(char)) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+
+    while let Some(pos) = rest.find('+') {
+        output.push_str(&rest[..pos]);
+        rest = &rest[pos..];
+
+        match decode_escape(rest) {
+            Some((decoded_char, len)) => {
+                on_decoded(decoded_char);
+                output.push(decoded_char);
+                rest = &rest[len..];
+            }
+            None => {
+                output.push('+');
+                rest = &rest[1..];
+            }
+        }
+    }
+    output.push_str(rest);
+
+    output
+}
+
+/// 이스케이프 하나의 길이 ("+x" + 16진수 4자리)
+const ESCAPE_LEN: usize = 6;
+
+/// `input` 앞부분의 이스케이프를 문자로 바꾸고, 소비한 바이트 수와 함께 반환합니다.
+fn decode_escape(input: &str) -> Option<(char, usize)> {
+    let unit = parse_escape(input)?;
+    if let Some(c) = char::from_u32(unit as u32) {
+        return Some((c, ESCAPE_LEN));
+    }
+
+    // 상위 서로게이트 뒤에는 하위 서로게이트 이스케이프가 바로 이어져야 합니다.
+    let low = parse_escape(&input[ESCAPE_LEN..])?;
+    let c = char::decode_utf16([unit, low]).next()?.ok()?;
+    Some((c, ESCAPE_LEN * 2))
+}
+
+/// `input`이 `+xNNNN` 또는 `+XNNNN`으로 시작하면 UTF-16 코드 유닛 값을 반환합니다.
+fn parse_escape(input: &str) -> Option<u16> {
+    let bytes = input.as_bytes();
+    if bytes.len() < ESCAPE_LEN || bytes[0] != b'+' || !matches!(bytes[1], b'x' | b'X') {
+        return None;
+    }
+    if !bytes[2..ESCAPE_LEN].iter().all(u8::is_ascii_hexdigit) {
+        return None;
+    }
+    u16::from_str_radix(&input[2..ESCAPE_LEN], 16).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_hangul_encode_decode() {
+        let original = "테스트@漢字㉷";
+        let encoded = hangul_encode(original);
+        println!("encoded: {}", encoded);
+        let decoded = hangul_decode(&encoded);
+        println!("decoded: {}", decoded);
+        assert_eq!(original, decoded);
+    }
+
+    #[test]
+    fn test_hangul_encode() {
+        let input = "Hello@세계";
+        let expected = "Hello+x0040+xC138+xACC4"; // 예시, 실제 인코딩 결과에 맞게 수정
+        let encoded = hangul_encode(input);
+        assert_eq!(encoded, expected);
+    }
+
+    #[test]
+    fn test_hangul_decode() {
+        let input = "Hello+x0040세계";
+        let expected = "Hello@세계";
+        let decoded = hangul_decode(input);
+        assert_eq!(decoded, expected);
+    }
+
+    #[test]
+    fn test_escape_supplementary_plane() {
+        let mut encoded = String::new();
+        write_escape(&mut encoded, 'X', '😀');
+        assert_eq!(encoded, "+XD83D+XDE00");
+        assert_eq!(hangul_decode(&encoded), "😀");
+        assert_eq!(hangul_decode("가+xD840+xDC0B나"), "가𠀋나");
+    }
+
+    #[test]
+    fn test_decode_unpaired_surrogate_is_left_as_is() {
+        assert_eq!(hangul_decode("+xD83D"), "+xD83D");
+        assert_eq!(hangul_decode("+xDE00+xD83D"), "+xDE00+xD83D");
+        assert_eq!(hangul_decode("+xD83D+xAC00"), "+xD83D가");
+    }
+
+    /// 원문에 이스케이프처럼 보이는 문자열이 있어도 그대로 돌아와야 합니다.
+    const ADVERSARIAL: &[&str] = &[
+        "+x0041",
+        "+X0041",
+        "+x0041가",
+        "++x0041",
+        "+x+x+x",
+        "+x002B",
+        "+x002Bx0041",
+        "+x",
+        "+X",
+        "+",
+        "x+",
+        "+xZZZZ",
+        "+x004",
+        "+x00가41",
+        "+xD83D",
+        "+xD83D+xDE00",
+        "😀+xDE00",
+        "가+xD83D",
+        "0x1F +x7F00 +X0000",
+        "https://example.com/?q=a+xyz&r=b+X1234",
+        "세계+x0040@",
+        "+x0000\0",
+    ];
+
+    #[test]
+    fn test_adversarial_inputs_roundtrip() {
+        for &input in ADVERSARIAL {
+            let encoded = hangul_encode(input);
+            assert_eq!(hangul_decode(&encoded), input, "encoded: {encoded}");
+        }
+    }
+
+    #[test]
+    fn test_literal_marker_is_escaped() {
+        assert_eq!(hangul_encode("+x0041"), "+x002Bx0041");
+        assert_eq!(hangul_encode("a+b"), "a+b");
+        assert_eq!(hangul_encode("+가"), "++xAC00");
+    }
+
+    #[test]
+    fn test_decode_keeps_invalid_escape_text() {
+        assert_eq!(hangul_decode("+xZZZZ"), "+xZZZZ");
+        assert_eq!(hangul_decode("+x00"), "+x00");
+    }
+
+    #[test]
+    fn test_sjis_unmappable() {
+        assert!(sjis_unmappable("おはよう、世界。ABC").is_empty());
+        assert_eq!(sjis_unmappable("a가b😀"), vec![(1, '가'), (5, '😀')]);
+    }
+
+    #[test]
+    fn test_encode_with_predicate() {
+        let encoded = encode_with("a😀+xb", |c| (!is_sjis_mappable(c)).then_some('X'));
+        assert_eq!(encoded, "a+XD83D+XDE00+x002Bxb");
+        assert_eq!(hangul_decode(&encoded), "a😀+xb");
+    }
+
+    /// Shift-JIS(CP932)로 표현되지만 엔진이 망가뜨려서 손으로 추가한 문자
+    const SJIS_MAPPABLE_SPECIAL_CHARS: &[char] = &[
+        'Ω', 'ⅰ', 'ⅱ', 'ⅲ', 'ⅳ', 'ⅴ', 'ⅵ', 'ⅶ', 'ⅷ', 'ⅸ', 'ⅹ', '①', '②', '③', '④', '⑤', '⑥', '⑦',
+        '⑧', '⑨', '⑩', '⑪', '⑫', '⑬', '⑭', '⑮', '＂', '＇',
+    ];
+
+    #[test]
+    fn test_generated_table_is_up_to_date() {
+        let computed = euc_kr_only_chars();
+        let committed: Vec<char> = (0..=0xFFFF)
+            .filter_map(char::from_u32)
+            .filter(|&c| EUC_KR_ONLY.contains(c))
+            .collect();
+        assert_eq!(
+            committed, computed,
+            "special_chars_table.rs is stale; run `cargo run --no-default-features --bin gen_special_chars > src/special_chars_table.rs`"
+        );
+    }
+
+    #[test]
+    fn test_hand_list_matches_computed_set() {
+        let computed = euc_kr_only_chars();
+        for c in (0..=0xFFFF).filter_map(char::from_u32) {
+            if !SPECIAL_CHARS.contains(c) {
+                continue;
+            }
+            assert_eq!(
+                computed.contains(&c),
+                !SJIS_MAPPABLE_SPECIAL_CHARS.contains(&c),
+                "{c:?} (U+{:04X}) drifted between SPECIAL_CHARS and the computed set",
+                c as u32
+            );
+        }
+        for c in SJIS_MAPPABLE_SPECIAL_CHARS {
+            assert!(
+                SPECIAL_CHARS.contains(*c),
+                "{c:?} is no longer in SPECIAL_CHARS"
+            );
+        }
+    }
+
+    #[test]
+    fn test_special_chars_lookup() {
+        assert!(needs_encoding('♥'));
+        assert!(needs_encoding('⒂'));
+        assert!(needs_encoding('乫'));
+        assert!(!needs_encoding('a'));
+        assert!(!needs_encoding('😀'));
+    }
+
+    proptest! {
+        #[test]
+        fn prop_decode_inverts_encode_marker_heavy(s in "[+xX0-9A-Fa-f가@😀]{0,32}") {
+            prop_assert_eq!(hangul_decode(&hangul_encode(&s)), s);
+        }
+
+        #[test]
+        fn prop_decode_inverts_encode(s in any::<String>()) {
+            prop_assert_eq!(hangul_decode(&hangul_encode(&s)), s);
+        }
+
+        #[test]
+        fn prop_escape_roundtrips_every_scalar(c in any::<char>()) {
+            let mut encoded = String::new();
+            write_escape(&mut encoded, 'x', c);
+            prop_assert_eq!(hangul_decode(&encoded), c.to_string());
+        }
+    }
+}
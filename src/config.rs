@@ -0,0 +1,377 @@
+use crate::{AsciiToken, EngineOptions, Field, HexEscape, PrivateUseArea, ProtectionStrategy};
+
+/// ezTrans XP 기본 설치 경로
+pub const DEFAULT_PATH: &str = "C:/Program Files (x86)/ChangShinSoft/ezTrans XP";
+/// 기본 DLL 파일 이름
+pub const DEFAULT_DLL_NAME: &str = "J2KEngine.dll";
+/// `J2K_InitializeEx`에 넘기는 기본 초기화 키
+pub const DEFAULT_INIT_KEY: &str = "CSUSER123455";
+
+/// `translate`가 사용할 엔진 함수
+///
+/// `Mmntw`는 UTF-16을 주고받고, 나머지는 Shift-JIS를 넘기고 EUC-KR을 돌려받습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TranslateMode {
+    /// Ehnd의 `J2K_TranslateMMNTW`가 있으면 사용하고, 없으면 `J2K_TranslateMMNT`를 사용합니다.
+    #[default]
+    Auto,
+    /// `J2K_TranslateChat`
+    Chat,
+    /// `J2K_TranslateFM`
+    Fm,
+    /// `J2K_TranslateMM`
+    Mm,
+    /// `J2K_TranslateMMEx`
+    #[cfg_attr(feature = "serde", serde(rename = "mmex"))]
+    MmEx,
+    /// `J2K_TranslateMMNT` (Shift-JIS 입력, EUC-KR 출력)
+    Mmnt,
+    /// `J2K_TranslateMMNTW` (UTF-16 입출력, Ehnd 필요)
+    Mmntw,
+}
+
+impl TranslateMode {
+    pub const ALL: [TranslateMode; 7] = [
+        TranslateMode::Auto,
+        TranslateMode::Chat,
+        TranslateMode::Fm,
+        TranslateMode::Mm,
+        TranslateMode::MmEx,
+        TranslateMode::Mmnt,
+        TranslateMode::Mmntw,
+    ];
+
+    /// 이 모드의 엔진 함수를 바인딩하는 Cargo 기능 이름
+    pub const fn feature(self) -> &'static str {
+        match self {
+            TranslateMode::Auto | TranslateMode::Mmnt => "translate-mmnt",
+            TranslateMode::Chat => "translate-chat",
+            TranslateMode::Fm => "translate-fm",
+            TranslateMode::Mm => "translate-mm",
+            TranslateMode::MmEx => "translate-mmex",
+            TranslateMode::Mmntw => "translate-mmntw",
+        }
+    }
+}
+
+/// 엔진을 초기화할 함수
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum InitMethod {
+    /// `J2K_InitializeEx`가 있으면 사용하고, 없으면 `J2K_Initialize`를 사용합니다.
+    #[default]
+    Auto,
+    /// `J2K_InitializeEx` (초기화 키와 데이터 폴더를 넘깁니다)
+    Ex,
+    /// `J2K_Initialize` (인자가 없어 초기화 키와 데이터 폴더 설정을 사용하지 않습니다)
+    Plain,
+}
+
+/// `translate_and_encode`에서 보호할 문자를 어떻게 감쌀지 정합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum EscapePolicy {
+    /// `+xNNNN` 텍스트 이스케이프 ([`HexEscape`])
+    #[default]
+    Hex,
+    /// 사용자 정의 영역 문자 ([`PrivateUseArea`])
+    PrivateUse,
+    /// ASCII 토큰 ([`AsciiToken`])
+    AsciiToken,
+    /// 보호하지 않고 그대로 번역합니다.
+    None,
+}
+
+impl EscapePolicy {
+    /// 정책에 맞는 보호 방법. `None`이면 보호하지 않습니다.
+    pub fn strategy(&self, extra_special_chars: &[char]) -> Option<Box<dyn ProtectionStrategy>> {
+        match self {
+            EscapePolicy::Hex => Some(Box::new(HexEscape::with_extra(
+                extra_special_chars.iter().copied(),
+            ))),
+            EscapePolicy::PrivateUse => Some(Box::new(PrivateUseArea)),
+            EscapePolicy::AsciiToken => Some(Box::new(AsciiToken)),
+            EscapePolicy::None => None,
+        }
+    }
+}
+
+/// [`EzTransLib`](crate::EzTransLib) 설정
+///
+/// [`EzTransConfig::builder`]로 만들거나, `serde` 기능을 켜고 TOML/JSON에서 읽을 수 있습니다.
+/// 지정하지 않은 값은 기본값을 사용합니다.
+///
+/// ```toml
+/// install_path = "D:/ezTrans XP"
+/// init_key = "CSUSER123455"
+/// init_method = "auto"
+/// translate_mode = "mmnt"
+/// escape_policy = "ascii_token"
+/// extra_special_chars = ["★", "☆"]
+///
+/// [options]
+/// field = 1
+/// hnj2han = true
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct EzTransConfig {
+    /// ezTrans XP 설치 경로
+    pub install_path: String,
+    /// 설치 경로 안의 DLL 파일 이름
+    pub dll_name: String,
+    /// 엔진 데이터 폴더. 없으면 `{install_path}/Dat`를 사용합니다.
+    pub data_dir: Option<String>,
+    /// `J2K_InitializeEx`에 넘기는 초기화 키
+    pub init_key: String,
+    /// 엔진을 초기화할 함수
+    pub init_method: InitMethod,
+    /// `translate`가 사용할 엔진 함수
+    pub translate_mode: TranslateMode,
+    /// `translate_and_encode`에서 사용할 보호 방법
+    pub escape_policy: EscapePolicy,
+    /// 기본 목록에 더해 이스케이프할 문자 ([`EscapePolicy::Hex`]에서만 사용)
+    pub extra_special_chars: Vec<char>,
+    /// 초기화한 뒤 적용할 엔진 옵션
+    pub options: EngineOptions,
+}
+
+impl Default for EzTransConfig {
+    fn default() -> Self {
+        EzTransConfig {
+            install_path: DEFAULT_PATH.to_string(),
+            dll_name: DEFAULT_DLL_NAME.to_string(),
+            data_dir: None,
+            init_key: DEFAULT_INIT_KEY.to_string(),
+            init_method: InitMethod::default(),
+            translate_mode: TranslateMode::default(),
+            escape_policy: EscapePolicy::default(),
+            extra_special_chars: Vec::new(),
+            options: EngineOptions::default(),
+        }
+    }
+}
+
+impl EzTransConfig {
+    pub fn builder() -> EzTransConfigBuilder {
+        EzTransConfigBuilder::default()
+    }
+
+    /// 불러올 DLL의 전체 경로
+    pub fn dll_path(&self) -> String {
+        format!("{}/{}", self.install_path, self.dll_name)
+    }
+
+    /// `J2K_InitializeEx`에 넘길 데이터 폴더
+    pub fn data_dir(&self) -> String {
+        match &self.data_dir {
+            Some(data_dir) => data_dir.clone(),
+            None => format!("{}/Dat", self.install_path),
+        }
+    }
+
+    /// TOML 문자열에서 설정을 읽습니다.
+    #[cfg(feature = "serde")]
+    pub fn from_toml_str(input: &str) -> Result<Self, crate::EzTransError> {
+        toml::from_str(input).map_err(|e| crate::EzTransError::ConfigError(e.to_string()))
+    }
+
+    /// JSON 문자열에서 설정을 읽습니다.
+    #[cfg(feature = "serde")]
+    pub fn from_json_str(input: &str) -> Result<Self, crate::EzTransError> {
+        serde_json::from_str(input).map_err(|e| crate::EzTransError::ConfigError(e.to_string()))
+    }
+
+    /// 설정 파일을 읽습니다. 확장자가 `.json`이면 JSON, 그 외에는 TOML로 읽습니다.
+    #[cfg(feature = "serde")]
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<Self, crate::EzTransError> {
+        let path = path.as_ref();
+        let input = std::fs::read_to_string(path)
+            .map_err(|e| crate::EzTransError::ConfigError(format!("{}: {}", path.display(), e)))?;
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json_str(&input),
+            _ => Self::from_toml_str(&input),
+        }
+    }
+}
+
+/// [`EzTransConfig`] 빌더
+#[derive(Debug, Clone, Default)]
+pub struct EzTransConfigBuilder {
+    config: EzTransConfig,
+}
+
+impl EzTransConfigBuilder {
+    pub fn install_path(mut self, install_path: impl Into<String>) -> Self {
+        self.config.install_path = install_path.into();
+        self
+    }
+
+    pub fn dll_name(mut self, dll_name: impl Into<String>) -> Self {
+        self.config.dll_name = dll_name.into();
+        self
+    }
+
+    pub fn data_dir(mut self, data_dir: impl Into<String>) -> Self {
+        self.config.data_dir = Some(data_dir.into());
+        self
+    }
+
+    pub fn init_key(mut self, init_key: impl Into<String>) -> Self {
+        self.config.init_key = init_key.into();
+        self
+    }
+
+    pub fn init_method(mut self, init_method: InitMethod) -> Self {
+        self.config.init_method = init_method;
+        self
+    }
+
+    pub fn translate_mode(mut self, translate_mode: TranslateMode) -> Self {
+        self.config.translate_mode = translate_mode;
+        self
+    }
+
+    pub fn escape_policy(mut self, escape_policy: EscapePolicy) -> Self {
+        self.config.escape_policy = escape_policy;
+        self
+    }
+
+    pub fn extra_special_chars(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.config.extra_special_chars.extend(chars);
+        self
+    }
+
+    /// 엔진 옵션을 통째로 바꿉니다.
+    pub fn options(mut self, options: EngineOptions) -> Self {
+        self.config.options = options;
+        self
+    }
+
+    pub fn field(mut self, field: Field) -> Self {
+        self.config.options.field = Some(field);
+        self
+    }
+
+    pub fn hnj2han(mut self, hnj2han: bool) -> Self {
+        self.config.options.hnj2han = Some(hnj2han);
+        self
+    }
+
+    pub fn del_jpn(mut self, del_jpn: bool) -> Self {
+        self.config.options.del_jpn = Some(del_jpn);
+        self
+    }
+
+    pub fn jwin(mut self, jwin: bool) -> Self {
+        self.config.options.jwin = Some(jwin);
+        self
+    }
+
+    pub fn build(self) -> EzTransConfig {
+        self.config
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults() {
+        let config = EzTransConfig::builder().build();
+        assert_eq!(
+            config.dll_path(),
+            "C:/Program Files (x86)/ChangShinSoft/ezTrans XP/J2KEngine.dll"
+        );
+        assert_eq!(
+            config.data_dir(),
+            "C:/Program Files (x86)/ChangShinSoft/ezTrans XP/Dat"
+        );
+        assert_eq!(config.init_key, DEFAULT_INIT_KEY);
+    }
+
+    #[test]
+    fn test_builder_overrides() {
+        let config = EzTransConfig::builder()
+            .install_path("D:/ez")
+            .dll_name("J2KEngineH.dll")
+            .data_dir("E:/Dat")
+            .translate_mode(TranslateMode::Mmnt)
+            .field(Field::new(2))
+            .build();
+        assert_eq!(config.dll_path(), "D:/ez/J2KEngineH.dll");
+        assert_eq!(config.data_dir(), "E:/Dat");
+        assert_eq!(config.translate_mode, TranslateMode::Mmnt);
+        assert_eq!(config.options.field, Some(Field::new(2)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_from_toml_and_json() {
+        let from_toml = EzTransConfig::from_toml_str(
+            r#"
+            install_path = "D:/ez"
+            translate_mode = "mmntw"
+            escape_policy = "ascii_token"
+            extra_special_chars = ["★"]
+
+            [options]
+            field = 1
+            hnj2han = true
+            "#,
+        )
+        .unwrap();
+        let from_json = EzTransConfig::from_json_str(
+            r#"{
+                "install_path": "D:/ez",
+                "translate_mode": "mmntw",
+                "escape_policy": "ascii_token",
+                "extra_special_chars": ["★"],
+                "options": { "field": 1, "hnj2han": true }
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(from_toml, from_json);
+        assert_eq!(from_toml.init_key, DEFAULT_INIT_KEY);
+        assert_eq!(from_toml.escape_policy, EscapePolicy::AsciiToken);
+        assert_eq!(from_toml.options.field, Some(Field::new(1)));
+        assert_eq!(from_toml.options.del_jpn, None);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_translate_mode_names() {
+        for (name, mode) in [("chat", TranslateMode::Chat), ("mmex", TranslateMode::MmEx)] {
+            let config =
+                EzTransConfig::from_toml_str(&format!("translate_mode = \"{}\"", name)).unwrap();
+            assert_eq!(config.translate_mode, mode);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_unknown_field_is_rejected() {
+        assert!(matches!(
+            EzTransConfig::from_toml_str("instal_path = \"D:/ez\""),
+            Err(crate::EzTransError::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+#[cfg(any(feature = "native-engine", test))]
+use crate::{codec, ShiftJisEscape, TransErr};
+use crate::{
+    run_cancellable, CancellationToken, Capabilities, EngineOptions, EzTransError, HexEscape,
+    ProtectionStrategy, StopHandle, TranslateMode, TranslateOptions,
+};
+
+/// 번역 엔진이 제공해야 하는 공통 인터페이스입니다.
+///
+/// [`EzTransLib`](crate::EzTransLib)는 실제 `J2KEngine.dll`을 사용하고,
+/// [`MockEngine`](crate::MockEngine)은 DLL 없이 규칙 테이블로 동작합니다.
+///
+/// 초기화와 종료는 구현체마다 따로 다룹니다. `EzTransLib`은 초기화된 핸들에만 이 트레이트를
+/// 구현하므로, 이 트레이트를 받는 코드는 항상 번역할 준비가 된 엔진을 받습니다.
+pub trait TranslationEngine {
+    /// 입력 문자열을 번역합니다.
+    fn translate(&self, input: &str) -> Result<String, EzTransError>;
+
+    /// 요청마다 다른 설정(`options`)으로 번역합니다.
+    ///
+    /// 기본 구현은 `encode`만 지원하고, 번역 모드나 엔진 옵션을 바꾸려고 하면 [`EzTransError::Unsupported`]를 반환합니다.
+    fn translate_request(
+        &self,
+        input: &str,
+        options: &TranslateOptions,
+    ) -> Result<String, EzTransError> {
+        if let Some(mode) = options.mode.filter(|mode| *mode != TranslateMode::Auto) {
+            return Err(EzTransError::Unsupported(format!("{:?} mode", mode)));
+        }
+        if !options.engine_options().is_empty() {
+            return Err(EzTransError::Unsupported("engine options".to_string()));
+        }
+        if options.encode {
+            self.translate_and_encode(input)
+        } else {
+            self.translate(input)
+        }
+    }
+
+    /// DLL이 내보내는 함수와 쓸 수 있는 번역 모드. DLL을 쓰지 않는 엔진은 `None`을 돌려줍니다.
+    fn capabilities(&self) -> Option<Capabilities> {
+        None
+    }
+
+    /// 다른 스레드에서 진행 중인 번역을 멈출 수 있는 핸들. 멈출 수 없는 엔진은 `None`을 돌려줍니다.
+    fn stop_handle(&self) -> Option<StopHandle> {
+        None
+    }
+
+    /// `cancel`이 취소되거나 `timeout`이 지나면 번역을 멈추고 [`TransErr::Cancelled`](crate::TransErr::Cancelled)나
+    /// [`TransErr::TimedOut`](crate::TransErr::TimedOut)을 반환합니다. 그 사이에 번역이 끝났다면 결과를 그대로 반환합니다.
+    /// 멈춘 뒤에도 엔진은 계속 사용할 수 있습니다.
+    fn translate_cancellable(
+        &self,
+        input: &str,
+        cancel: &CancellationToken,
+        timeout: Option<Duration>,
+    ) -> Result<String, EzTransError> {
+        run_cancellable(self.stop_handle(), cancel, timeout, || {
+            self.translate(input)
+        })
+    }
+
+    /// 사용자 사전을 다시 읽습니다. 사용자 사전이 없는 엔진은 아무것도 하지 않습니다.
+    fn reload_user_dict(&self) -> Result<(), EzTransError> {
+        Ok(())
+    }
+
+    /// 한글이나 특수 문자를 인코딩한 뒤 번역하고, 번역 결과를 다시 디코딩합니다.
+    fn translate_and_encode(&self, input: &str) -> Result<String, EzTransError> {
+        self.translate_protected(input, &HexEscape::new())
+    }
+
+    /// `strategy`로 보호할 문자를 자리표시자로 바꿔 번역한 뒤 되돌립니다.
+    ///
+    /// 번역 중에 사라진 자리표시자가 있으면 [`TransErr::PlaceholderLost`](crate::TransErr::PlaceholderLost)를 반환합니다.
+    fn translate_protected(
+        &self,
+        input: &str,
+        strategy: &dyn ProtectionStrategy,
+    ) -> Result<String, EzTransError> {
+        translate_protected_with(input, strategy, |text| self.translate(text))
+    }
+}
+
+/// `strategy`로 보호한 문자열을 `translate`로 번역한 뒤 되돌립니다.
+pub(crate) fn translate_protected_with(
+    input: &str,
+    strategy: &dyn ProtectionStrategy,
+    translate: impl FnOnce(&str) -> Result<String, EzTransError>,
+) -> Result<String, EzTransError> {
+    let protected = strategy.protect(input);
+    if protected.placeholders.is_empty() {
+        // 인코딩/디코딩 없이 직접 번역
+        return translate(input);
+    }
+
+    let translated = translate(&protected.text)?;
+    strategy
+        .restore(&translated, &protected)
+        .map_err(EzTransError::TranslationError)
+}
+
+/// Shift-JIS로 표현할 수 없는 문자를 [`ShiftJisEscape`]로 감싸서 `translate`로 번역한 뒤 되돌립니다.
+///
+/// 번역 중에 이스케이프가 사라지거나 깨지면, 그 문자가 입력에 나타나는 바이트 오프셋을 담은
+/// [`TransErr::Unmappable`]을 반환합니다.
+#[cfg(any(feature = "native-engine", test))]
+pub(crate) fn translate_sjis_with(
+    input: &str,
+    translate: impl FnOnce(&str) -> Result<String, EzTransError>,
+) -> Result<String, EzTransError> {
+    let protected = ShiftJisEscape.protect(input);
+    if protected.placeholders.is_empty() {
+        return translate(input);
+    }
+
+    let translated = translate(&protected.text)?;
+    ShiftJisEscape
+        .restore(&translated, &protected)
+        .map_err(|error| match error {
+            TransErr::PlaceholderLost(lost) => TransErr::Unmappable(
+                codec::sjis_unmappable(input)
+                    .into_iter()
+                    .filter(|(_, c)| lost.contains(c))
+                    .collect(),
+            ),
+            error => error,
+        })
+        .map_err(EzTransError::TranslationError)
+}
+
+/// `overrides`를 적용한 채로 `translate`를 실행하고, 바꾼 옵션을 `current`의 값으로 되돌립니다.
+///
+/// 현재 값을 모르는 옵션은 되돌릴 수 없으므로 아무것도 바꾸지 않고 [`EzTransError::Unsupported`]를 반환합니다.
+/// 번역이 실패해도 되돌리며, 번역 에러가 되돌리기 에러보다 먼저 보고됩니다.
+pub(crate) fn with_overrides(
+    current: &EngineOptions,
+    overrides: &EngineOptions,
+    apply: impl Fn(&EngineOptions) -> Result<(), EzTransError>,
+    translate: impl FnOnce() -> Result<String, EzTransError>,
+) -> Result<String, EzTransError> {
+    if overrides.is_empty() {
+        return translate();
+    }
+
+    let undo = current.undo_for(overrides)?;
+    let result = apply(overrides).and_then(|()| translate());
+    let restored = apply(&undo);
+    let translated = result?;
+    restored?;
+    Ok(translated)
+}
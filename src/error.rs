@@ -1,29 +1,47 @@
-use std::{ffi::NulError, fmt};
+use std::{
+    ffi::{c_int, NulError},
+    fmt,
+};
 
 use thiserror::Error;
 
+use crate::pe::Machine;
+
 #[derive(Error, Debug, Clone)]
 pub enum EzTransError {
     #[error("Failed to load library: {0}")]
     LibraryLoadError(String),
+    #[error("Invalid DLL {path}: {error}")]
+    InvalidDll { path: String, error: PeError },
     #[error("Failed to get symbol: {0}")]
     SymbolLoadError(String),
-    #[error("Failed to initialize")]
-    InitializationError,
+    #[error("Failed to initialize: {entry_point} returned {code}")]
+    InitializationError {
+        entry_point: &'static str,
+        code: c_int,
+    },
     #[error("Failed to translate")]
     TranslationError(TransErr),
     #[error("Failed to terminate")]
     TerminationError,
-    #[error("DLL path not set")]
-    DllPathNotSet,
     #[error("Invalid string: {0}")]
     InvalidString(NulError),
-    #[error("OnceLock error: {0}")]
-    OnceLockError(String),
     #[error("Shared Memory Error: {0}")]
     SharedMemoryError(String),
+    #[error("Transport error: {0}")]
+    TransportError(String),
+    #[error("Engine thread error: {0}")]
+    EngineThreadError(String),
     #[error("{0}")]
     Utf16Error(String),
+    #[error("Invalid config: {0}")]
+    ConfigError(String),
+    #[error("{function} returned {code}")]
+    EngineCallFailed { function: &'static str, code: c_int },
+    #[error("Not supported by this engine: {0}")]
+    Unsupported(String),
+    #[error("Feature `{0}` is not enabled")]
+    FeatureDisabled(&'static str),
 }
 
 #[derive(Error, Debug, Clone)]
@@ -34,6 +52,14 @@ pub enum TransErr {
     Failed,
     ///EUC-KR decoding failed
     EucKrDecodeFailed,
+    ///Placeholders for these protected characters did not survive translation
+    PlaceholderLost(Vec<char>),
+    ///Characters (with byte offsets) that Shift-JIS cannot represent whose escapes did not survive translation
+    Unmappable(Vec<(usize, char)>),
+    ///Translation was cancelled through a CancellationToken
+    Cancelled,
+    ///Translation did not finish within the timeout
+    TimedOut,
 }
 impl fmt::Display for TransErr {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -41,6 +67,41 @@ impl fmt::Display for TransErr {
             TransErr::NullPointer => write!(f, "TRANSLATE func returned a null pointer"),
             TransErr::Failed => write!(f, "Translation failed"),
             TransErr::EucKrDecodeFailed => write!(f, "EUC-KR decoding failed"),
+            TransErr::PlaceholderLost(chars) => {
+                write!(f, "Placeholders lost in translation: {:?}", chars)
+            }
+            TransErr::Unmappable(chars) => {
+                write!(f, "Characters not representable in Shift-JIS: ")?;
+                for (i, (offset, c)) in chars.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?} (U+{:04X}) at {}", c, *c as u32, offset)?;
+                }
+                Ok(())
+            }
+            TransErr::Cancelled => write!(f, "Translation cancelled"),
+            TransErr::TimedOut => write!(f, "Translation timed out"),
         }
     }
 }
+
+#[derive(Error, Debug, Clone)]
+pub enum PeError {
+    #[error("{0}")]
+    Io(String),
+    #[error("not a PE file (missing MZ header)")]
+    NotMz,
+    #[error("not a PE file (missing PE signature)")]
+    NotPe,
+    #[error("unknown optional header magic 0x{0:04X}")]
+    UnknownOptionalHeader(u16),
+    #[error("file is truncated in {0}")]
+    Truncated(&'static str),
+    #[error("{what} points outside every section (RVA 0x{rva:08X})")]
+    BadRva { rva: u32, what: &'static str },
+    #[error("not a DLL")]
+    NotDll,
+    #[error("DLL is built for {dll}, but this process is {process}")]
+    WrongMachine { dll: Machine, process: Machine },
+}
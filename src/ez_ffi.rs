@@ -1,12 +1,11 @@
 #![allow(non_camel_case_types)]
-use std::{
-    ffi::{c_char, c_int, c_void},
-    sync::{LazyLock, OnceLock},
-};
+use std::ffi::{c_char, c_int, c_void};
+use std::path::Path;
+use std::sync::{Arc, Mutex, PoisonError};
 
 use libloading::{Library, Symbol};
 
-use crate::EzTransError;
+use crate::{pe, Capabilities, EzTransError};
 
 // Type definitions for all EzTrans engine functions
 #[cfg(feature = "free-mem")]
@@ -50,211 +49,391 @@ pub type J2K_TranslateMMNT = unsafe extern "stdcall" fn(c_int, *const c_char) ->
 #[cfg(feature = "translate-mmntw")]
 pub type J2K_TranslateMMNTW = unsafe extern "stdcall" fn(c_int, *const u16) -> *mut u16;
 
-// DLL 경로를 저장할 전역 변수
-pub static DLL_PATH: OnceLock<String> = OnceLock::new();
+/// 불러온 `J2KEngine.dll`과 그 안의 엔진 함수들입니다.
+///
+/// 엔진 함수는 호출할 때마다 DLL에서 찾으며, DLL에 없는 함수를 호출하면 `Err(SymbolLoadError)`를 반환합니다.
+/// 함수 포인터를 밖으로 내주지 않으므로 이 값이 drop되거나 [`unload`](Self::unload)된 뒤에는 엔진 함수를 호출할 수 없습니다.
+///
+/// 엔진의 상태는 프로세스 전체에서 하나이므로, 같은 DLL은 한 번에 한 `EngineLibrary`만 불러올 수 있습니다.
+///
+/// # Safety
+///
+/// 엔진 함수는 `J2KEngine.dll`의 규약대로 호출해야 합니다. 번역과 설정 함수는 엔진을 초기화한 뒤에만 호출하고,
+/// 문자열 인자는 NUL로 끝나야 하며, 번역 함수가 돌려준 포인터는 [`free_mem`](Self::free_mem)으로 한 번만 해제해야 합니다.
+pub struct EngineLibrary {
+    path: String,
+    // StopHandle이 다른 스레드에서 DLL을 붙잡아 둘 수 있도록 Arc로 공유합니다.
+    module: Arc<LoadedModule>,
+}
 
-pub static LIBRARY: LazyLock<Result<Library, EzTransError>> = LazyLock::new(|| {
-    let path = DLL_PATH.get().ok_or(EzTransError::DllPathNotSet)?;
-    unsafe { Library::new(path).map_err(|e| EzTransError::LibraryLoadError(e.to_string().into())) }
-});
+/// 불러온 DLL과 그 등록. DLL이 해제될 때까지 같은 DLL을 다시 불러올 수 없습니다.
+struct LoadedModule {
+    // 필드는 선언 순서대로 drop되므로, DLL을 먼저 해제한 뒤 등록을 지웁니다.
+    library: Library,
+    _registration: ModuleRegistration,
+}
 
-//메모리 해제 함수. EzTrans 엔진에 의해 할당된 메모리를 해제합니다.
-#[cfg(feature = "free-mem")]
-pub static FREE_MEM: LazyLock<Result<Symbol<J2K_FreeMem>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_FreeMem")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string().into()))
-    });
-/// 현재 설정된 사용자 사전의 우선순위를 가져옵니다.
-#[cfg(feature = "get-prior-dict")]
-pub static GET_PRIOR_DICT: LazyLock<Result<Symbol<J2K_GetPriorDict>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_GetPriorDict")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 특정 속성의 현재 값을 가져옵니다.
-#[cfg(feature = "get-property")]
-pub static GET_PROPERTY: LazyLock<Result<Symbol<J2K_GetProperty>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_GetProperty")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// EzTrans 엔진을 초기화합니다. 기본 설정을 사용합니다.
-#[cfg(feature = "initialize")]
-pub static INITIALIZE: LazyLock<Result<Symbol<J2K_Initialize>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_Initialize")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// EzTrans 엔진을 초기화합니다. 사용자 지정 설정을 사용할 수 있습니다.
-#[cfg(feature = "initialize-ex")]
-pub static INITIALIZE_EX: LazyLock<Result<Symbol<J2K_InitializeEx>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_InitializeEx")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string().into()))
-    });
-/// 사용자 사전을 다시 로드합니다.
-#[cfg(feature = "reload-user-dict")]
-pub static RELOAD_USER_DICT: LazyLock<Result<Symbol<J2K_ReloadUserDict>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_ReloadUserDict")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 일본어 문장 구분 기능을 설정합니다.
-#[cfg(feature = "set-del-jpn")]
-pub static SET_DEL_JPN: LazyLock<Result<Symbol<J2K_SetDelJPN>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_SetDelJPN")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 번역 분야를 설정합니다. 예: 일반, 과학기술, 컴퓨터 등.
-#[cfg(feature = "set-field")]
-pub static SET_FIELD: LazyLock<Result<Symbol<J2K_SetField>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_SetField")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 한자를 한글로 변환하는 옵션을 설정합니다.
-#[cfg(feature = "set-hnj2han")]
-pub static SET_HNJ2HAN: LazyLock<Result<Symbol<J2K_SetHnj2han>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_SetHnj2han")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// J-Win 모드를 설정합니다. (일본어 윈도우 호환성 관련)
-#[cfg(feature = "set-jwin")]
-pub static SET_JWIN: LazyLock<Result<Symbol<J2K_SetJWin>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_SetJWin")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 사용자 사전의 우선순위를 설정합니다.
-#[cfg(feature = "set-prior-dict")]
-pub static SET_PRIOR_DICT: LazyLock<Result<Symbol<J2K_SetPriorDict>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_SetPriorDict")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 특정 속성의 값을 설정합니다.
-#[cfg(feature = "set-property")]
-pub static SET_PROPERTY: LazyLock<Result<Symbol<J2K_SetProperty>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_SetProperty")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 현재 진행 중인 번역 작업을 중지합니다.
-#[cfg(feature = "stop-translation")]
-pub static STOP_TRANSLATION: LazyLock<Result<Symbol<J2K_StopTranslation>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_StopTranslation")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// EzTrans 엔진을 종료하고 사용된 리소스를 해제합니다.
-#[cfg(feature = "terminate")]
-pub static TERMINATE: LazyLock<Result<Symbol<J2K_Terminate>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_Terminate")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string().into()))
-    });
-/// 채팅 모드에서 텍스트를 번역합니다.
-#[cfg(feature = "translate-chat")]
-pub static TRANSLATE_CHAT: LazyLock<Result<Symbol<J2K_TranslateChat>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_TranslateChat")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 전문 번역 모드에서 텍스트를 번역합니다.
-#[cfg(feature = "translate-fm")]
-pub static TRANSLATE_FM: LazyLock<Result<Symbol<J2K_TranslateFM>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_TranslateFM")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 일반 번역 모드에서 텍스트를 번역합니다.
-#[cfg(feature = "translate-mm")]
-pub static TRANSLATE_MM: LazyLock<Result<Symbol<J2K_TranslateMM>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_TranslateMM")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// 확장된 일반 번역 모드에서 텍스트를 번역합니다.
-#[cfg(feature = "translate-mmex")]
-pub static TRANSLATE_MMEX: LazyLock<Result<Symbol<J2K_TranslateMMEx>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_TranslateMMEx")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// No Thread 모드에서 텍스트를 번역합니다. (멀티스레드 환경에서 유용)
-#[cfg(feature = "translate-mmnt")]
-pub static TRANSLATE_MMNT: LazyLock<Result<Symbol<J2K_TranslateMMNT>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_TranslateMMNT")
-            .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
-/// No Thread 모드에서 와이드 문자열(Unicode) 텍스트를 번역합니다.
-#[cfg(feature = "translate-mmntw")]
-pub static TRANSLATE_MMNTW: LazyLock<Result<Symbol<J2K_TranslateMMNTW>, EzTransError>> =
-    LazyLock::new(|| unsafe {
-        LIBRARY
-            .as_ref()
-            .map_err(|e| e.clone())?
-            .get(b"J2K_TranslateMMNTW")
+impl EngineLibrary {
+    /// `path`의 DLL을 불러옵니다.
+    ///
+    /// `path`에 파일이 있으면 불러오기 전에 PE 헤더를 검사해서, 64비트 DLL이나 깨진 파일은 [`EzTransError::InvalidDll`]로 알려 줍니다.
+    /// 파일이 없으면(`J2KEngine.dll`처럼 이름만 준 경우 등) 운영체제의 DLL 검색 경로로 찾도록 그대로 넘깁니다.
+    /// 같은 DLL을 다른 `EngineLibrary`가 이미 불러온 상태라면 [`EzTransError::LibraryLoadError`]를 반환합니다.
+    pub fn load(path: &str) -> Result<Self, EzTransError> {
+        if Path::new(path).exists() {
+            pe::check_dll(path)?;
+        }
+
+        let library = unsafe {
+            Library::new(path)
+                .map_err(|e| EzTransError::LibraryLoadError(format!("{}: {}", path, e)))?
+        };
+        let (library, handle) = module_handle(library);
+        let registration = ModuleRegistration::register(handle).ok_or_else(|| {
+            EzTransError::LibraryLoadError(format!(
+                "{}: already loaded by another EzTransLib in this process",
+                path
+            ))
+        })?;
+
+        Ok(EngineLibrary {
+            path: path.to_string(),
+            module: Arc::new(LoadedModule {
+                library,
+                _registration: registration,
+            }),
+        })
+    }
+
+    /// `name` 함수를 찾습니다. 돌려받은 심볼은 `self`보다 오래 쓸 수 없습니다.
+    unsafe fn get<T>(&self, name: &[u8]) -> Result<Symbol<'_, T>, EzTransError> {
+        self.module
+            .library
+            .get::<T>(name)
             .map_err(|e| EzTransError::SymbolLoadError(e.to_string()))
-    });
+    }
+
+    /// `J2K_FreeMem`: 엔진이 할당한 메모리를 해제합니다.
+    ///
+    /// # Safety
+    /// `ptr`은 이 엔진의 번역 함수가 돌려준, 아직 해제하지 않은 포인터여야 합니다.
+    #[cfg(feature = "free-mem")]
+    pub unsafe fn free_mem(&self, ptr: *mut c_void) -> Result<(), EzTransError> {
+        self.get::<J2K_FreeMem>(b"J2K_FreeMem")?(ptr);
+        Ok(())
+    }
+
+    /// `J2K_GetPriorDict`: 현재 설정된 사용자 사전의 우선순위를 가져옵니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "get-prior-dict")]
+    pub unsafe fn get_prior_dict(&self) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_GetPriorDict>(b"J2K_GetPriorDict")?())
+    }
+
+    /// `J2K_GetProperty`: 특정 속성의 현재 값을 가져옵니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "get-property")]
+    pub unsafe fn get_property(&self, id: c_int) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_GetProperty>(b"J2K_GetProperty")?(id))
+    }
+
+    /// `J2K_Initialize`: 기본 설정으로 엔진을 초기화합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "initialize")]
+    pub unsafe fn initialize(&self) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_Initialize>(b"J2K_Initialize")?())
+    }
+
+    /// `J2K_InitializeEx`: 초기화 키와 데이터 폴더를 지정해서 엔진을 초기화합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "initialize-ex")]
+    pub unsafe fn initialize_ex(
+        &self,
+        init_str: *const c_char,
+        home_dir: *const c_char,
+    ) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_InitializeEx>(b"J2K_InitializeEx")?(
+            init_str, home_dir,
+        ))
+    }
+
+    /// `J2K_ReloadUserDict`: 사용자 사전을 다시 로드합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "reload-user-dict")]
+    pub unsafe fn reload_user_dict(&self) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_ReloadUserDict>(b"J2K_ReloadUserDict")?())
+    }
+
+    /// `J2K_SetDelJPN`: 번역하지 못한 일본어를 지울지 설정합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "set-del-jpn")]
+    pub unsafe fn set_del_jpn(&self, value: c_int) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_SetDelJPN>(b"J2K_SetDelJPN")?(value))
+    }
+
+    /// `J2K_SetField`: 번역 분야를 설정합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "set-field")]
+    pub unsafe fn set_field(&self, value: c_int) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_SetField>(b"J2K_SetField")?(value))
+    }
+
+    /// `J2K_SetHnj2han`: 한자를 한글로 변환할지 설정합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "set-hnj2han")]
+    pub unsafe fn set_hnj2han(&self, value: c_int) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_SetHnj2han>(b"J2K_SetHnj2han")?(value))
+    }
+
+    /// `J2K_SetJWin`: J-Win 호환 모드를 설정합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "set-jwin")]
+    pub unsafe fn set_jwin(&self, value: c_int) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_SetJWin>(b"J2K_SetJWin")?(value))
+    }
+
+    /// `J2K_SetPriorDict`: 우선 적용할 사용자 사전을 지정합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "set-prior-dict")]
+    pub unsafe fn set_prior_dict(&self, dict: *const c_char) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_SetPriorDict>(b"J2K_SetPriorDict")?(dict))
+    }
+
+    /// `J2K_SetProperty`: 특정 속성의 값을 설정합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "set-property")]
+    pub unsafe fn set_property(&self, id: c_int, value: c_int) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_SetProperty>(b"J2K_SetProperty")?(id, value))
+    }
+
+    /// `J2K_Terminate`: 엔진을 종료하고 사용된 리소스를 해제합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "terminate")]
+    pub unsafe fn terminate(&self) -> Result<c_int, EzTransError> {
+        Ok(self.get::<J2K_Terminate>(b"J2K_Terminate")?())
+    }
+
+    /// `J2K_TranslateChat`: 채팅 모드로 번역합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "translate-chat")]
+    pub unsafe fn translate_chat(&self, text: *const c_char) -> Result<*mut c_char, EzTransError> {
+        Ok(self.get::<J2K_TranslateChat>(b"J2K_TranslateChat")?(text))
+    }
+
+    /// `J2K_TranslateFM`: 전문 번역 모드로 번역합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "translate-fm")]
+    pub unsafe fn translate_fm(&self, text: *const c_char) -> Result<*mut c_char, EzTransError> {
+        Ok(self.get::<J2K_TranslateFM>(b"J2K_TranslateFM")?(text))
+    }
+
+    /// `J2K_TranslateMM`: 일반 번역 모드로 번역합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "translate-mm")]
+    pub unsafe fn translate_mm(&self, text: *const c_char) -> Result<*mut c_char, EzTransError> {
+        Ok(self.get::<J2K_TranslateMM>(b"J2K_TranslateMM")?(text))
+    }
+
+    /// `J2K_TranslateMMEx`: 확장된 일반 번역 모드로 번역합니다.
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "translate-mmex")]
+    pub unsafe fn translate_mmex(
+        &self,
+        data0: c_int,
+        text: *const c_char,
+    ) -> Result<*mut c_char, EzTransError> {
+        Ok(self.get::<J2K_TranslateMMEx>(b"J2K_TranslateMMEx")?(
+            data0, text,
+        ))
+    }
+
+    /// `J2K_TranslateMMNT`: No Thread 모드로 번역합니다. (멀티스레드 환경에서 유용)
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "translate-mmnt")]
+    pub unsafe fn translate_mmnt(
+        &self,
+        data0: c_int,
+        text: *const c_char,
+    ) -> Result<*mut c_char, EzTransError> {
+        Ok(self.get::<J2K_TranslateMMNT>(b"J2K_TranslateMMNT")?(
+            data0, text,
+        ))
+    }
+
+    /// `J2K_TranslateMMNTW`: No Thread 모드로 와이드 문자열(Unicode)을 번역합니다. (Ehnd)
+    ///
+    /// # Safety
+    /// [`EngineLibrary`]의 호출 규약을 따라야 합니다.
+    #[cfg(feature = "translate-mmntw")]
+    pub unsafe fn translate_mmntw(
+        &self,
+        data0: c_int,
+        text: *const u16,
+    ) -> Result<*mut u16, EzTransError> {
+        Ok(self.get::<J2K_TranslateMMNTW>(b"J2K_TranslateMMNTW")?(
+            data0, text,
+        ))
+    }
+
+    /// 불러온 DLL의 경로
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// DLL이 `name` 함수를 내보내는지 확인합니다. 기능을 켜지 않은 함수도 찾아봅니다.
+    pub fn has_export(&self, name: &str) -> bool {
+        unsafe {
+            self.module
+                .library
+                .get::<*const c_void>(name.as_bytes())
+                .is_ok()
+        }
+    }
+
+    /// DLL이 내보내는 `J2K_*` 함수와 이 빌드의 기능을 정리합니다.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::probe(&self.path, |name| self.has_export(name))
+    }
+
+    /// 다른 스레드에서 `J2K_StopTranslation`을 호출하는 핸들. 핸들이 살아 있는 동안 DLL은 해제되지 않습니다.
+    #[cfg(feature = "stop-translation")]
+    pub fn stop_handle(&self) -> Result<crate::StopHandle, EzTransError> {
+        // 함수 포인터는 DLL을 붙잡고 있는 클로저 밖으로 나가지 않습니다.
+        let stop_translation: J2K_StopTranslation =
+            *unsafe { self.get::<J2K_StopTranslation>(b"J2K_StopTranslation")? };
+        let module = self.module.clone();
+        Ok(crate::StopHandle::new(move || {
+            let _module = &module;
+            unsafe { stop_translation() };
+        }))
+    }
+
+    /// DLL을 해제합니다. 해제에 실패하거나 아직 [`StopHandle`](crate::StopHandle)이 DLL을 사용 중이면 에러를 반환합니다.
+    /// (drop할 때는 에러를 무시합니다.)
+    pub fn unload(self) -> Result<(), EzTransError> {
+        let module = Arc::try_unwrap(self.module).map_err(|_| {
+            EzTransError::LibraryLoadError(format!("{} is still in use", self.path))
+        })?;
+        // 해제에 실패해도 등록은 지웁니다.
+        module
+            .library
+            .close()
+            .map_err(|e| EzTransError::LibraryLoadError(e.to_string()))
+    }
+}
+
+/// 불러온 DLL의 모듈 핸들. 같은 DLL을 여러 번 불러와도 운영 체제는 같은 핸들을 돌려줍니다.
+fn module_handle(library: Library) -> (Library, usize) {
+    #[cfg(unix)]
+    use libloading::os::unix::Library as RawLibrary;
+    #[cfg(windows)]
+    use libloading::os::windows::Library as RawLibrary;
+
+    let raw = RawLibrary::from(library).into_raw();
+    // SAFETY: 방금 `into_raw`로 꺼낸 핸들을 그대로 되돌립니다.
+    let library = unsafe { RawLibrary::from_raw(raw) };
+    (library.into(), raw as usize)
+}
+
+/// 프로세스에서 `EngineLibrary`가 불러온 모듈 핸들 목록
+static LOADED_MODULES: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+
+/// [`LOADED_MODULES`]에 등록한 모듈. drop되면 등록을 지웁니다.
+struct ModuleRegistration(usize);
+
+impl ModuleRegistration {
+    /// 이미 등록된 모듈이면 `None`을 반환합니다.
+    fn register(handle: usize) -> Option<Self> {
+        let mut loaded = LOADED_MODULES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        if loaded.contains(&handle) {
+            return None;
+        }
+        loaded.push(handle);
+        Some(ModuleRegistration(handle))
+    }
+}
+
+impl Drop for ModuleRegistration {
+    fn drop(&mut self) {
+        LOADED_MODULES
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .retain(|handle| *handle != self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_module_can_be_registered_once_at_a_time() {
+        // 실제 모듈 핸들과 겹치지 않도록 정렬되지 않은 값을 사용합니다.
+        let first = ModuleRegistration::register(0x9001).unwrap();
+        assert!(ModuleRegistration::register(0x9001).is_none());
+        drop(first);
+        assert!(ModuleRegistration::register(0x9001).is_some());
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_bare_name_is_resolved_by_the_loader() {
+        // 현재 폴더에 없는 이름은 PE 검사 없이 운영체제의 검색 경로로 찾습니다.
+        // 테스트는 동시에 돌므로 DLL을 불러오는 테스트마다 다른 라이브러리를 씁니다.
+        let library = EngineLibrary::load("libc.so.6").unwrap();
+        assert!(matches!(
+            EngineLibrary::load("libc.so.6"),
+            Err(EzTransError::LibraryLoadError(message)) if message.contains("already loaded")
+        ));
+        drop(library);
+        EngineLibrary::load("libc.so.6").unwrap();
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn test_registration_lives_as_long_as_shared_module() {
+        let library = EngineLibrary::load("libm.so.6").unwrap();
+        // StopHandle처럼 DLL을 붙잡아 두는 복제본이 남아 있으면 다시 불러올 수 없습니다.
+        let kept = library.module.clone();
+        drop(library);
+        assert!(EngineLibrary::load("libm.so.6").is_err());
+        drop(kept);
+        EngineLibrary::load("libm.so.6").unwrap();
+    }
+}
@@ -1,104 +1,578 @@
-use crate::{ez_ffi, EzTransError, TransErr, DLL_PATH, LIBRARY, TRANSLATE_MMNTW};
-
-use std::collections::HashSet;
+use crate::engine::{translate_protected_with, translate_sjis_with, with_overrides};
+#[cfg(feature = "stop-translation")]
+use crate::StopHandle;
+use crate::{
+    codec, Capabilities, EngineLibrary, EngineOptions, EzTransConfig, EzTransError, Field,
+    InitMethod, ProtectionStrategy, TransErr, TranslateMode, TranslateOptions, TranslationEngine,
+};
+#[cfg(any(feature = "get-property", feature = "set-property"))]
+use crate::{EngineProperty, PropertySnapshot};
+
+use std::cell::Cell;
+#[cfg(any(
+    feature = "get-prior-dict",
+    feature = "get-property",
+    feature = "initialize",
+    feature = "initialize-ex",
+    feature = "reload-user-dict",
+    feature = "set-del-jpn",
+    feature = "set-field",
+    feature = "set-hnj2han",
+    feature = "set-jwin",
+    feature = "set-prior-dict",
+    feature = "set-property"
+))]
+use std::ffi::c_int;
 use std::ffi::{c_void, CStr, CString};
-use std::fmt::Write;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
 use std::os::raw::c_char;
+use std::ptr;
+
+/// DLL만 불러온 상태. [`EzTransLib::initialize`]만 호출할 수 있습니다.
+pub struct Loaded;
+/// 엔진이 초기화된 상태. 번역 함수를 사용할 수 있습니다.
+pub struct Initialized;
+/// [`EzTransLib::terminate`]로 엔진을 종료한 상태. drop되면 DLL이 해제됩니다.
+pub struct Terminated;
+
+mod sealed {
+    pub trait Sealed {}
+    impl Sealed for super::Loaded {}
+    impl Sealed for super::Initialized {}
+    impl Sealed for super::Terminated {}
+}
 
-use std::sync::LazyLock;
-
-const SPECIAL_CHARS: LazyLock<HashSet<char>> = LazyLock::new(|| {
-    [
-        '↔', '◁', '◀', '▷', '▶', '♤', '♠', '♡', '♥', '♧', '♣', '⊙', '◈', '▣', '◐', '◑', '▒', '▤',
-        '▥', '▨', '▧', '▦', '▩', '♨', '☏', '☎', '☜', '☞', '↕', '↗', '↙', '↖', '↘', '♩', '♬', '㉿',
-        '㈜', '㏇', '™', '㏂', '㏘', '＂', '＇', '∼', 'ˇ', '˘', '˝', '¡', '˚', '˙', '˛', '¿', 'ː',
-        '∏', '￦', '℉', '€', '㎕', '㎖', '㎗', 'ℓ', '㎘', '㎣', '㎤', '㎥', '㎦', '㎙', '㎚', '㎛',
-        '㎟', '㎠', '㎢', '㏊', '㎍', '㏏', '㎈', '㎉', '㏈', '㎧', '㎨', '㎰', '㎱', '㎲', '㎳',
-        '㎴', '㎵', '㎶', '㎷', '㎸', '㎀', '㎁', '㎂', '㎃', '㎄', '㎺', '㎻', '㎼', '㎽', '㎾',
-        '㎿', '㎐', '㎑', '㎒', '㎓', '㎔', 'Ω', '㏀', '㏁', '㎊', '㎋', '㎌', '㏖', '㏅', '㎭',
-        '㎮', '㎯', '㏛', '㎩', '㎪', '㎫', '㎬', '㏝', '㏐', '㏓', '㏃', '㏉', '㏜', '㏆', '┒',
-        '┑', '┚', '┙', '┖', '┕', '┎', '┍', '┞', '┟', '┡', '┢', '┦', '┧', '┪', '┭', '┮', '┵', '┶',
-        '┹', '┺', '┽', '┾', '╀', '╁', '╃', '╄', '╅', '╆', '╇', '╈', '╉', '╊', '┱', '┲', 'ⅰ', 'ⅱ',
-        'ⅲ', 'ⅳ', 'ⅴ', 'ⅵ', 'ⅶ', 'ⅷ', 'ⅸ', 'ⅹ', '½', '⅓', '⅔', '¼', '¾', '⅛', '⅜', '⅝', '⅞', 'ⁿ',
-        '₁', '₂', '₃', '₄', 'Ŋ', 'đ', 'Ħ', 'Ĳ', 'Ŀ', 'Ł', 'Œ', 'Ŧ', 'ħ', 'ı', 'ĳ', 'ĸ', 'ŀ', 'ł',
-        'œ', 'ŧ', 'ŋ', 'ŉ', '㉠', '㉡', '㉢', '㉣', '㉤', '㉥', '㉦', '㉧', '㉨', '㉩', '㉪', '㉫',
-        '㉬', '㉭', '㉮', '㉯', '㉰', '㉱', '㉲', '㉳', '㉴', '㉵', '㉶', '㉷', '㉸', '㉹', '㉺',
-        '㉻', '㈀', '㈁', '㈂', '㈃', '㈄', '㈅', '㈆', '㈇', '㈈', '㈉', '㈊', '㈋', '㈌', '㈍',
-        '㈎', '㈏', '㈐', '㈑', '㈒', '㈓', '㈔', '㈕', '㈖', '㈗', '㈘', '㈙', '㈚', '㈛', 'ⓐ',
-        'ⓑ', 'ⓒ', 'ⓓ', 'ⓔ', 'ⓕ', 'ⓖ', 'ⓗ', 'ⓘ', 'ⓙ', 'ⓚ', 'ⓛ', 'ⓜ', 'ⓝ', 'ⓞ', 'ⓟ', 'ⓠ', 'ⓡ', 'ⓢ',
-        'ⓣ', 'ⓤ', 'ⓥ', 'ⓦ', 'ⓧ', 'ⓨ', 'ⓩ', '①', '②', '③', '④', '⑤', '⑥', '⑦', '⑧', '⑨', '⑩', '⑪',
-        '⑫', '⑬', '⑭', '⑮', '⒜', '⒝', '⒞', '⒟', '⒠', '⒡', '⒢', '⒣', '⒤', '⒥', '⒦', '⒧', '⒨', '⒩',
-        '⒪', '⒫', '⒬', '⒭', '⒮', '⒯', '⒰', '⒱', '⒲', '⒳', '⒴', '⒵', '⑴', '⑵', '⑶', '⑷', '⑸', '⑹',
-        '⑺', '⑻', '⑼', '⑽', '⑾', '⑿', '⒀', '⒁', '⒂',
-    ]
-    .iter()
-    .cloned()
-    .collect()
-});
-
-pub struct EzTransLib {
-    pub ehnd_support: bool,
+/// [`EzTransLib`]의 수명 주기 상태 (`Loaded` → `Initialized` → `Terminated`)
+pub trait EngineState: sealed::Sealed {
+    /// drop될 때 `J2K_Terminate`를 호출해야 하는지 여부
+    const TERMINATE_ON_DROP: bool;
+}
+
+impl EngineState for Loaded {
+    const TERMINATE_ON_DROP: bool = false;
+}
+impl EngineState for Initialized {
+    const TERMINATE_ON_DROP: bool = true;
+}
+impl EngineState for Terminated {
+    const TERMINATE_ON_DROP: bool = false;
 }
 
-const DEFAULT_PATH: &str = "C:/Program Files (x86)/ChangShinSoft/ezTrans XP";
+/// `J2KEngine.dll`을 사용하는 번역 엔진 핸들입니다.
+///
+/// 수명 주기가 타입으로 표현됩니다. [`EzTransLib::new`]는 `EzTransLib<Loaded>`를 돌려주고,
+/// `initialize`를 거쳐야 번역 함수가 있는 `EzTransLib<Initialized>`가 됩니다.
+/// `terminate`는 핸들을 소비하므로 종료한 엔진을 다시 쓰거나 두 번 종료할 수 없습니다.
+#[cfg_attr(
+    feature = "native-engine",
+    doc = r#"
+```compile_fail,E0599
+# use eztrans_sys::EzTransLib;
+let ez_trans = EzTransLib::new(None).unwrap();
+ez_trans.translate("おはようございます。"); // 초기화하지 않은 엔진에는 translate가 없습니다.
+```"#
+)]
+pub struct EzTransLib<S: EngineState = Initialized> {
+    pub ehnd_support: bool,
+    /// 이 인스턴스가 불러온 DLL. `EzTransLib`이 drop되면 (초기화된 경우 엔진을 종료한 뒤) 해제됩니다.
+    library: EngineLibrary,
+    /// 이 핸들을 만들 때 사용한 설정
+    config: EzTransConfig,
+    /// `translate_and_encode`에서 사용하는 보호 방법 (`config.escape_policy`로 만듭니다)
+    escape: Option<Box<dyn ProtectionStrategy>>,
+    /// 이 핸들에서 마지막으로 설정에 성공한 엔진 옵션. 엔진에서 읽어 올 수 없으므로 직접 기록합니다.
+    options: Cell<EngineOptions>,
+    state: PhantomData<S>,
+}
 
-impl EzTransLib {
+impl EzTransLib<Loaded> {
+    /// `folder_path`(기본값: ezTrans XP 설치 경로)의 `J2KEngine.dll`을 불러옵니다.
+    ///
+    /// DLL은 인스턴스마다 따로 불러오므로, 경로를 바꿔 가며 여러 번 호출하거나 실패한 뒤 다시 시도할 수 있습니다.
+    /// 다만 엔진 상태를 공유하지 않도록, 같은 DLL을 불러온 핸들이 살아 있는 동안에는 다시 불러올 수 없습니다.
     pub fn new(folder_path: Option<&str>) -> Result<Self, EzTransError> {
-        let dll_path = format!("{}/J2KEngine.dll", folder_path.unwrap_or(DEFAULT_PATH)); //"C:/Program Files (x86)/ChangShinSoft/ezTrans XP/J2KEngine.dll"
-        DLL_PATH
-            .set(dll_path.to_string())
-            .map_err(|e| EzTransError::OnceLockError(e.into()))?;
+        let mut config = EzTransConfig::builder();
+        if let Some(folder_path) = folder_path {
+            config = config.install_path(folder_path);
+        }
+        Self::with_config(config.build())
+    }
+
+    /// 설정에 지정된 DLL을 불러옵니다. 초기화 키와 데이터 폴더 등은 `initialize`에서 사용합니다.
+    pub fn with_config(config: EzTransConfig) -> Result<Self, EzTransError> {
+        let library = EngineLibrary::load(&config.dll_path())?;
+
+        let ehnd_support = library.has_export("J2K_TranslateMMNTW");
+        let escape = config.escape_policy.strategy(&config.extra_special_chars);
+
+        Ok(EzTransLib {
+            ehnd_support,
+            library,
+            config,
+            escape,
+            options: Cell::new(EngineOptions::default()),
+            state: PhantomData,
+        })
+    }
+
+    /// 엔진을 초기화합니다. 실패하면 핸들이 drop되면서 DLL도 해제됩니다.
+    ///
+    /// 설정의 `init_method`에 따라 `J2K_InitializeEx`나 `J2K_Initialize`를 호출합니다.
+    /// 기본값([`InitMethod::Auto`])은 DLL이 `J2K_InitializeEx`를 내보내지 않을 때 `J2K_Initialize`로 대신합니다.
+    /// 엔진이 실패를 돌려주면 호출한 함수와 반환값을 담은 [`EzTransError::InitializationError`]를 반환합니다.
+    ///
+    /// `init_str`과 `folder_dir`을 생략하면 설정의 초기화 키와 데이터 폴더를 사용합니다.
+    /// `folder_dir`을 주면 `{folder_dir}/Dat`를 데이터 폴더로 사용합니다. (`J2K_Initialize`는 둘 다 사용하지 않습니다.)
+    /// `J2K_Initialize`를 호출하게 되는데 `init_str`이나 `folder_dir`을 넘겼다면 무시하지 않고 [`EzTransError::ConfigError`]를 반환합니다.
+    /// 초기화한 뒤 설정의 엔진 옵션([`EngineOptions`])을 적용합니다.
+    pub fn initialize(
+        self,
+        init_str: Option<&str>,
+        folder_dir: Option<&str>,
+    ) -> Result<EzTransLib<Initialized>, EzTransError> {
+        let use_ex = use_initialize_ex(
+            self.config.init_method,
+            self.has_initialize_ex(),
+            self.has_initialize(),
+            init_str.is_some() || folder_dir.is_some(),
+        )?;
+        if use_ex {
+            self.initialize_ex(init_str, folder_dir)?;
+        } else {
+            self.initialize_plain()?;
+        }
 
-        LIBRARY.as_ref().map_err(|e| e.clone())?;
+        // 옵션 적용에 실패해도 초기화된 핸들이 drop되면서 엔진이 종료됩니다.
+        let initialized: EzTransLib<Initialized> = self.into_state();
+        initialized.apply_options(&initialized.config.options)?;
+        Ok(initialized)
+    }
+}
 
-        let ehnd_support = TRANSLATE_MMNTW.is_ok();
+impl EzTransLib<Loaded> {
+    fn has_initialize_ex(&self) -> bool {
+        #[cfg(feature = "initialize-ex")]
+        return self.library.has_export("J2K_InitializeEx");
+        #[cfg(not(feature = "initialize-ex"))]
+        false
+    }
 
-        Ok(EzTransLib { ehnd_support })
+    fn has_initialize(&self) -> bool {
+        #[cfg(feature = "initialize")]
+        return self.library.has_export("J2K_Initialize");
+        #[cfg(not(feature = "initialize"))]
+        false
     }
 
-    pub fn initialize(
+    fn initialize_ex(
         &self,
         init_str: Option<&str>,
         folder_dir: Option<&str>,
     ) -> Result<(), EzTransError> {
-        let init_str = CString::new(init_str.unwrap_or("CSUSER123455"))
-            .map_err(EzTransError::InvalidString)?;
-        let home_dir = CString::new(format!("{}/Dat", folder_dir.unwrap_or(DEFAULT_PATH))) //C:/Program Files (x86)/ChangShinSoft/ezTrans XP/Dat
+        let init_str = CString::new(init_str.unwrap_or(&self.config.init_key))
             .map_err(EzTransError::InvalidString)?;
+        let data_dir = match folder_dir {
+            Some(folder_dir) => format!("{}/Dat", folder_dir),
+            None => self.config.data_dir(),
+        };
+        let home_dir = CString::new(data_dir).map_err(EzTransError::InvalidString)?;
+
+        #[cfg(feature = "initialize-ex")]
+        {
+            let ret = unsafe {
+                self.library
+                    .initialize_ex(init_str.as_ptr(), home_dir.as_ptr())?
+            };
+            check_init("J2K_InitializeEx", ret)
+        }
+        #[cfg(not(feature = "initialize-ex"))]
+        {
+            let _ = (init_str, home_dir);
+            Err(EzTransError::FeatureDisabled("initialize-ex"))
+        }
+    }
+
+    fn initialize_plain(&self) -> Result<(), EzTransError> {
+        #[cfg(feature = "initialize")]
+        {
+            let ret = unsafe { self.library.initialize()? };
+            check_init("J2K_Initialize", ret)
+        }
+        #[cfg(not(feature = "initialize"))]
+        Err(EzTransError::FeatureDisabled("initialize"))
+    }
+}
+
+impl<S: EngineState> EzTransLib<S> {
+    /// `translate_and_encode`에서 기본 목록에 더해 이스케이프할 문자를 추가합니다.
+    /// ([`EscapePolicy::Hex`](crate::EscapePolicy::Hex)에서만 사용됩니다.)
+    pub fn with_special_chars(mut self, chars: impl IntoIterator<Item = char>) -> Self {
+        self.config.extra_special_chars.extend(chars);
+        self.escape = self
+            .config
+            .escape_policy
+            .strategy(&self.config.extra_special_chars);
+        self
+    }
+
+    /// 이 핸들을 만들 때 사용한 설정
+    pub fn config(&self) -> &EzTransConfig {
+        &self.config
+    }
 
-        let initialize_ex = ez_ffi::INITIALIZE_EX.as_ref().map_err(|e| e.clone())?;
-        let ret = unsafe { initialize_ex(init_str.as_ptr(), home_dir.as_ptr()) };
-        if ret == 1 {
+    /// 이 핸들로 마지막에 설정한 번역 분야. 엔진에서 읽어 온 값이 아니며, 아직 설정하지 않았다면 `None`입니다.
+    pub fn last_field(&self) -> Option<Field> {
+        self.options.get().field
+    }
+
+    /// 이 핸들에서 설정한 엔진 옵션. 한 번도 설정하지 않은 항목은 `None`입니다.
+    pub fn options(&self) -> EngineOptions {
+        self.options.get()
+    }
+
+    /// 설정에 성공한 옵션을 기록합니다.
+    #[cfg(any(
+        feature = "set-del-jpn",
+        feature = "set-field",
+        feature = "set-hnj2han",
+        feature = "set-jwin",
+        feature = "set-property"
+    ))]
+    fn record_options(&self, options: &EngineOptions) {
+        let mut current = self.options.get();
+        current.merge(options);
+        self.options.set(current);
+    }
+
+    /// 한글 문자나 특수 문자는 "+x" 또는 "+X" 접두사와 함께 16진수 유니코드 값으로 변환됩니다. 그 외의 문자는 그대로 유지됩니다.
+    /// 왜 필요? 이지트랜스 엔진은 한글 문자나 특수 문자를 처리할 때 문제가 발생할 수 있습니다. 이를 방지하기 위해 문자를 변환한다고 추측하고 있습니다.
+    pub fn hangul_encode(&self, input: &str) -> String {
+        codec::hangul_encode(input)
+    }
+
+    /// 한글 문자를 판별합니다.
+    pub const fn is_hangul_range(&self, code: u32) -> bool {
+        codec::is_hangul_range(code)
+    }
+
+    /// "+x" 또는 "+X" 접두사와 함께 16진수 유니코드 값으로 변환된 문자를 원래 문자로 변환합니다.
+    pub fn hangul_decode(&self, input: &str) -> String {
+        codec::hangul_decode(input)
+    }
+
+    /// 불러온 DLL의 경로
+    pub fn dll_path(&self) -> &str {
+        self.library.path()
+    }
+
+    /// DLL이 실제로 내보내는 함수와 이 빌드에서 쓸 수 있는 함수를 확인합니다.
+    /// 처음 호출할 때 `SymbolLoadError`를 받기 전에 무엇이 빠졌는지 알려 줄 때 사용합니다.
+    pub fn capabilities(&self) -> Capabilities {
+        self.library.capabilities()
+    }
+
+    fn terminate_engine(&self) -> Result<(), EzTransError> {
+        let ret = unsafe { self.library.terminate()? };
+
+        if ret == 0 {
             Ok(())
         } else {
-            Err(EzTransError::InitializationError)
+            Err(EzTransError::TerminationError)
+        }
+    }
+
+    /// Drop을 거치지 않고 같은 DLL을 다른 상태의 핸들로 옮깁니다.
+    fn into_state<T: EngineState>(self) -> EzTransLib<T> {
+        let this = ManuallyDrop::new(self);
+        // SAFETY: `this`는 다시 drop되지 않으므로 각 필드를 정확히 한 번만 옮깁니다.
+        unsafe {
+            EzTransLib {
+                ehnd_support: this.ehnd_support,
+                library: ptr::read(&this.library),
+                config: ptr::read(&this.config),
+                escape: ptr::read(&this.escape),
+                options: ptr::read(&this.options),
+                state: PhantomData,
+            }
         }
     }
+}
 
+impl EzTransLib<Initialized> {
+    /// 설정의 `escape_policy`로 보호할 문자를 감싸서 번역합니다.
     pub fn translate_and_encode(&self, input: &str) -> Result<String, EzTransError> {
-        // 한글이나 특수 문자가 있는지 확인
-        let needs_encoding = input.chars().any(|c| {
-            c == '@' || c == '\0' || self.is_hangul_range(c as u32) || self.needs_encoding(c)
+        match &self.escape {
+            Some(strategy) => self.translate_protected(input, strategy.as_ref()),
+            None => self.translate(input),
+        }
+    }
+
+    /// 설정의 `translate_mode`에 맞는 엔진 함수로 번역합니다.
+    pub fn translate(&self, input: &str) -> Result<String, EzTransError> {
+        self.translate_with(self.config.translate_mode, input)
+    }
+
+    /// `mode`에 해당하는 엔진 함수로 번역합니다.
+    ///
+    /// 해당 기능을 켜지 않고 빌드했다면 [`EzTransError::FeatureDisabled`]를,
+    /// 불러온 DLL에 함수가 없다면 [`EzTransError::SymbolLoadError`]를 반환합니다.
+    /// 어떤 모드를 쓸 수 있는지는 [`supported_modes`](Self::supported_modes)로 확인할 수 있습니다.
+    pub fn translate_with(&self, mode: TranslateMode, input: &str) -> Result<String, EzTransError> {
+        match mode {
+            TranslateMode::Auto if self.ehnd_support => self.translate_mmntw(input),
+            TranslateMode::Auto | TranslateMode::Mmnt => self.translate_mmnt(input),
+            TranslateMode::Mmntw => self.translate_mmntw(input),
+            #[cfg(feature = "translate-chat")]
+            TranslateMode::Chat => self.translate_chat(input),
+            #[cfg(feature = "translate-fm")]
+            TranslateMode::Fm => self.translate_fm(input),
+            #[cfg(feature = "translate-mm")]
+            TranslateMode::Mm => self.translate_mm(input),
+            #[cfg(feature = "translate-mmex")]
+            TranslateMode::MmEx => self.translate_mmex(input),
+            #[allow(unreachable_patterns)]
+            mode => Err(EzTransError::FeatureDisabled(mode.feature())),
+        }
+    }
+
+    /// 이 빌드와 불러온 DLL로 `mode`를 사용할 수 있는지 확인합니다.
+    pub fn supports(&self, mode: TranslateMode) -> bool {
+        match mode {
+            TranslateMode::Auto => {
+                self.supports(TranslateMode::Mmnt) || self.supports(TranslateMode::Mmntw)
+            }
+            TranslateMode::Mmnt => self.library.has_export("J2K_TranslateMMNT"),
+            TranslateMode::Mmntw => self.library.has_export("J2K_TranslateMMNTW"),
+            #[cfg(feature = "translate-chat")]
+            TranslateMode::Chat => self.library.has_export("J2K_TranslateChat"),
+            #[cfg(feature = "translate-fm")]
+            TranslateMode::Fm => self.library.has_export("J2K_TranslateFM"),
+            #[cfg(feature = "translate-mm")]
+            TranslateMode::Mm => self.library.has_export("J2K_TranslateMM"),
+            #[cfg(feature = "translate-mmex")]
+            TranslateMode::MmEx => self.library.has_export("J2K_TranslateMMEx"),
+            #[allow(unreachable_patterns)]
+            _ => false,
+        }
+    }
+
+    /// 사용할 수 있는 번역 모드 목록 ([`TranslateMode::ALL`] 순서)
+    pub fn supported_modes(&self) -> Vec<TranslateMode> {
+        TranslateMode::ALL
+            .into_iter()
+            .filter(|mode| self.supports(*mode))
+            .collect()
+    }
+
+    /// 번역 분야를 설정합니다. 엔진이 0이 아닌 값을 돌려주면 [`EzTransError::EngineCallFailed`]를 반환하고,
+    /// [`last_field`](Self::last_field)는 이전 값을 유지합니다.
+    /// 엔진은 현재 분야를 읽는 함수를 내보내지 않으므로, 이 핸들 밖에서 바꾼 분야는 알 수 없습니다.
+    #[cfg(feature = "set-field")]
+    pub fn set_field(&self, field: Field) -> Result<(), EzTransError> {
+        check_call("J2K_SetField", unsafe {
+            self.library.set_field(field.code())?
+        })?;
+        self.record_options(&EngineOptions {
+            field: Some(field),
+            ..Default::default()
         });
+        Ok(())
+    }
 
-        let result = if needs_encoding {
-            let encoded = self.hangul_encode(input);
-            let translated = self.translate(&encoded)?;
-            self.hangul_decode(&translated)
-        } else {
-            // 인코딩/디코딩 없이 직접 번역
-            self.translate(input)?
-        };
+    /// 엔진 속성 값을 읽습니다.
+    #[cfg(feature = "get-property")]
+    pub fn get_property(&self, property: EngineProperty) -> Result<c_int, EzTransError> {
+        unsafe { self.library.get_property(property.id()) }
+    }
 
-        Ok(result)
+    /// 엔진 속성 값을 바꿉니다. 엔진이 0이 아닌 값을 돌려주면 [`EzTransError::EngineCallFailed`]를 반환합니다.
+    #[cfg(feature = "set-property")]
+    pub fn set_property(&self, property: EngineProperty, value: c_int) -> Result<(), EzTransError> {
+        check_call("J2K_SetProperty", unsafe {
+            self.library.set_property(property.id(), value)?
+        })
     }
 
-    pub fn translate(&self, input: &str) -> Result<String, EzTransError> {
-        if self.ehnd_support {
-            self.translate_mmntw(input)
-        } else {
-            self.translate_mmnt(input)
+    /// `properties`의 현재 값을 저장합니다.
+    ///
+    /// 엔진의 모든 속성을 저장하지 않습니다. 엔진은 속성 번호를 공개하지 않으므로, 바꿀 속성의 번호를
+    /// 호출한 쪽에서 넘겨야 하며 넘긴 속성만 [`restore`](Self::restore)로 되돌릴 수 있습니다.
+    /// 빈 목록을 넘기면 [`EzTransError::ConfigError`]를 반환합니다.
+    #[cfg(feature = "get-property")]
+    pub fn snapshot(
+        &self,
+        properties: impl IntoIterator<Item = EngineProperty>,
+    ) -> Result<PropertySnapshot, EzTransError> {
+        PropertySnapshot::capture(properties, |property| self.get_property(property))
+    }
+
+    /// [`snapshot`](Self::snapshot)으로 저장한 값을 되돌립니다.
+    ///
+    /// 하나가 실패해도 나머지 속성은 계속 되돌리고, 첫 번째 에러를 반환합니다.
+    #[cfg(feature = "set-property")]
+    pub fn restore(&self, snapshot: &PropertySnapshot) -> Result<(), EzTransError> {
+        let mut result = Ok(());
+        for (property, value) in snapshot.iter() {
+            let ret = self.set_property(property, value);
+            if result.is_ok() {
+                result = ret;
+            }
+        }
+        result
+    }
+
+    /// 현재 우선 사용자 사전 번호를 읽습니다.
+    #[cfg(feature = "get-prior-dict")]
+    pub fn get_prior_dict(&self) -> Result<c_int, EzTransError> {
+        unsafe { self.library.get_prior_dict() }
+    }
+
+    /// 우선 적용할 사용자 사전을 지정합니다. 엔진이 0이 아닌 값을 돌려주면 [`EzTransError::EngineCallFailed`]를 반환합니다.
+    #[cfg(feature = "set-prior-dict")]
+    pub fn set_prior_dict(&self, dict: &str) -> Result<(), EzTransError> {
+        let dict = CString::new(dict).map_err(EzTransError::InvalidString)?;
+        check_call("J2K_SetPriorDict", unsafe {
+            self.library.set_prior_dict(dict.as_ptr())?
+        })?;
+        Ok(())
+    }
+
+    /// `Dat` 폴더의 사용자 사전을 다시 읽습니다. 엔진을 다시 시작하지 않아도 사전 수정이 반영됩니다.
+    #[cfg(feature = "reload-user-dict")]
+    pub fn reload_user_dict(&self) -> Result<(), EzTransError> {
+        check_call("J2K_ReloadUserDict", unsafe {
+            self.library.reload_user_dict()?
+        })?;
+        Ok(())
+    }
+
+    /// 한자를 한글로 변환할지 설정합니다.
+    #[cfg(feature = "set-hnj2han")]
+    pub fn set_hnj2han(&self, enabled: bool) -> Result<(), EzTransError> {
+        check_call("J2K_SetHnj2han", unsafe {
+            self.library.set_hnj2han(enabled as c_int)?
+        })?;
+        self.record_options(&EngineOptions {
+            hnj2han: Some(enabled),
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    /// 번역하지 못한 일본어를 결과에서 지울지 설정합니다.
+    #[cfg(feature = "set-del-jpn")]
+    pub fn set_del_jpn(&self, enabled: bool) -> Result<(), EzTransError> {
+        check_call("J2K_SetDelJPN", unsafe {
+            self.library.set_del_jpn(enabled as c_int)?
+        })?;
+        self.record_options(&EngineOptions {
+            del_jpn: Some(enabled),
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    /// J-Win 호환 모드를 설정합니다.
+    #[cfg(feature = "set-jwin")]
+    pub fn set_jwin(&self, enabled: bool) -> Result<(), EzTransError> {
+        check_call("J2K_SetJWin", unsafe {
+            self.library.set_jwin(enabled as c_int)?
+        })?;
+        self.record_options(&EngineOptions {
+            jwin: Some(enabled),
+            ..Default::default()
+        });
+        Ok(())
+    }
+
+    /// `options`에서 `None`이 아닌 항목을 모두 설정합니다.
+    ///
+    /// 해당 함수 기능이 꺼져 있으면 [`EzTransError::FeatureDisabled`]를 반환합니다.
+    /// 중간에 실패하면 그 앞의 항목은 이미 적용된 상태로 남습니다.
+    pub fn apply_options(&self, options: &EngineOptions) -> Result<(), EzTransError> {
+        if let Some(field) = options.field {
+            #[cfg(feature = "set-field")]
+            self.set_field(field)?;
+            #[cfg(not(feature = "set-field"))]
+            {
+                let _ = field;
+                return Err(EzTransError::FeatureDisabled("set-field"));
+            }
+        }
+        if let Some(hnj2han) = options.hnj2han {
+            #[cfg(feature = "set-hnj2han")]
+            self.set_hnj2han(hnj2han)?;
+            #[cfg(not(feature = "set-hnj2han"))]
+            {
+                let _ = hnj2han;
+                return Err(EzTransError::FeatureDisabled("set-hnj2han"));
+            }
+        }
+        if let Some(del_jpn) = options.del_jpn {
+            #[cfg(feature = "set-del-jpn")]
+            self.set_del_jpn(del_jpn)?;
+            #[cfg(not(feature = "set-del-jpn"))]
+            {
+                let _ = del_jpn;
+                return Err(EzTransError::FeatureDisabled("set-del-jpn"));
+            }
         }
+        if let Some(jwin) = options.jwin {
+            #[cfg(feature = "set-jwin")]
+            self.set_jwin(jwin)?;
+            #[cfg(not(feature = "set-jwin"))]
+            {
+                let _ = jwin;
+                return Err(EzTransError::FeatureDisabled("set-jwin"));
+            }
+        }
+        Ok(())
+    }
+
+    /// `overrides`를 이번 번역에만 적용합니다. 번역이 끝나면 (실패해도) 바꾼 옵션을 이전 값으로 되돌립니다.
+    ///
+    /// 엔진에서 옵션 값을 읽어 올 수 없으므로, 설정 파일이나 `set_*`로 이 핸들에서 설정한 적이 없는 옵션은
+    /// 되돌릴 값을 몰라 바꾸지 않고 [`EzTransError::Unsupported`]를 반환합니다.
+    /// 번역 에러가 되돌리기 에러보다 먼저 보고됩니다.
+    pub fn translate_with_options(
+        &self,
+        input: &str,
+        overrides: &EngineOptions,
+    ) -> Result<String, EzTransError> {
+        self.with_overrides(overrides, || self.translate(input))
+    }
+
+    /// 요청 하나에만 번역 모드, 인코딩 여부, 엔진 옵션을 바꿔서 번역합니다.
+    /// 엔진 옵션은 [`translate_with_options`](Self::translate_with_options)처럼 번역이 끝나면 되돌립니다.
+    pub fn translate_request(
+        &self,
+        input: &str,
+        options: &TranslateOptions,
+    ) -> Result<String, EzTransError> {
+        let mode = options.mode.unwrap_or(self.config.translate_mode);
+        self.with_overrides(&options.engine_options(), || match &self.escape {
+            Some(strategy) if options.encode => {
+                translate_protected_with(input, strategy.as_ref(), |text| {
+                    self.translate_with(mode, text)
+                })
+            }
+            _ => self.translate_with(mode, input),
+        })
+    }
+
+    /// `overrides`를 적용한 채로 `translate`를 실행하고, 바꾼 옵션을 되돌립니다.
+    fn with_overrides(
+        &self,
+        overrides: &EngineOptions,
+        translate: impl FnOnce() -> Result<String, EzTransError>,
+    ) -> Result<String, EzTransError> {
+        with_overrides(
+            &self.options.get(),
+            overrides,
+            |options| self.apply_options(options),
+            translate,
+        )
     }
 
     /// EHND를 사용하여 번역합니다.
@@ -106,8 +580,7 @@ impl EzTransLib {
         // Convert input to UTF-16/ 끝에 NULL 문자를 추가
         let input_wide: Vec<u16> = input.encode_utf16().chain(std::iter::once(0)).collect();
 
-        let translate_mmntw = ez_ffi::TRANSLATE_MMNTW.as_ref().map_err(|e| e.clone())?;
-        let ret = unsafe { translate_mmntw(0, input_wide.as_ptr() as *mut u16) };
+        let ret = unsafe { self.library.translate_mmntw(0, input_wide.as_ptr())? };
 
         if ret.is_null() {
             return Err(EzTransError::TranslationError(TransErr::NullPointer));
@@ -116,126 +589,199 @@ impl EzTransLib {
         // 안전하게 UTF-16 문자열의 길이를 찾기
         let result = unsafe {
             let len = (0..).find(|&i| *ret.add(i) == 0).unwrap_or(0);
-            String::from_utf16(&std::slice::from_raw_parts(ret, len))
+            String::from_utf16(std::slice::from_raw_parts(ret, len))
                 .map_err(|e| EzTransError::Utf16Error(e.to_string()))?
         };
-        self.free_memory(ret as *mut c_void)?;
+        unsafe { self.free_memory(ret as *mut c_void)? };
         Ok(result)
     }
 
+    /// Shift-JIS 입력을 받는 `J2K_TranslateMMNT`로 번역합니다.
+    ///
+    /// Shift-JIS로 표현할 수 없는 문자는 미리 찾아서 `+XNNNN` 이스케이프로 보호한 뒤 되돌립니다.
+    /// 이스케이프가 번역 중에 깨지면 되돌리지 못한 문자를 담은 [`TransErr::PlaceholderLost`]를 반환합니다.
     pub fn translate_mmnt(&self, input: &str) -> Result<String, EzTransError> {
-        // Convert input to Shift-JIS
-        let input_sjis = encoding_rs::SHIFT_JIS.encode(input).0.to_vec();
+        self.translate_narrow(input, |text| unsafe {
+            self.library.translate_mmnt(0, text)
+        })
+    }
+
+    /// `J2K_TranslateChat`으로 번역합니다. 입출력 변환은 [`translate_mmnt`](Self::translate_mmnt)와 같습니다.
+    #[cfg(feature = "translate-chat")]
+    pub fn translate_chat(&self, input: &str) -> Result<String, EzTransError> {
+        self.translate_narrow(input, |text| unsafe { self.library.translate_chat(text) })
+    }
+
+    /// `J2K_TranslateFM`으로 번역합니다. 입출력 변환은 [`translate_mmnt`](Self::translate_mmnt)와 같습니다.
+    #[cfg(feature = "translate-fm")]
+    pub fn translate_fm(&self, input: &str) -> Result<String, EzTransError> {
+        self.translate_narrow(input, |text| unsafe { self.library.translate_fm(text) })
+    }
 
-        let translate_mmnt = ez_ffi::TRANSLATE_MMNT.as_ref().map_err(|e| e.clone())?;
-        let ret = unsafe { translate_mmnt(0, input_sjis.as_ptr() as *mut c_char) };
+    /// `J2K_TranslateMM`으로 번역합니다. 입출력 변환은 [`translate_mmnt`](Self::translate_mmnt)와 같습니다.
+    #[cfg(feature = "translate-mm")]
+    pub fn translate_mm(&self, input: &str) -> Result<String, EzTransError> {
+        self.translate_narrow(input, |text| unsafe { self.library.translate_mm(text) })
+    }
+
+    /// `J2K_TranslateMMEx`로 번역합니다. 입출력 변환은 [`translate_mmnt`](Self::translate_mmnt)와 같습니다.
+    #[cfg(feature = "translate-mmex")]
+    pub fn translate_mmex(&self, input: &str) -> Result<String, EzTransError> {
+        self.translate_narrow(input, |text| unsafe {
+            self.library.translate_mmex(0, text)
+        })
+    }
+
+    /// Shift-JIS를 받고 EUC-KR을 돌려주는 엔진 함수로 번역합니다.
+    /// Shift-JIS로 표현할 수 없는 문자는 `+XNNNN` 이스케이프로 보호하고, 그 이스케이프가 번역 중에 사라지면
+    /// [`TransErr::Unmappable`]로 그 문자와 위치를 알려 줍니다.
+    fn translate_narrow(
+        &self,
+        input: &str,
+        call: impl Fn(*const c_char) -> Result<*mut c_char, EzTransError>,
+    ) -> Result<String, EzTransError> {
+        translate_sjis_with(input, |text| self.translate_sjis(text, &call))
+    }
+
+    /// Shift-JIS로 모두 표현할 수 있는 문자열을 `call`로 번역하고, 돌려받은 EUC-KR 문자열을 해제합니다.
+    fn translate_sjis(
+        &self,
+        input: &str,
+        call: &impl Fn(*const c_char) -> Result<*mut c_char, EzTransError>,
+    ) -> Result<String, EzTransError> {
+        // Convert input to Shift-JIS / 끝에 NULL 문자를 추가
+        // 표현할 수 없는 문자는 translate_narrow에서 이스케이프했으므로 인코딩은 실패하지 않습니다.
+        let (input_sjis, _, _) = encoding_rs::SHIFT_JIS.encode(input);
+        let mut input_sjis = input_sjis.into_owned();
+        input_sjis.push(0);
+
+        let ret = call(input_sjis.as_ptr() as *const c_char)?;
 
         if ret.is_null() {
             return Err(EzTransError::TranslationError(TransErr::NullPointer));
         }
         // Convert the result from EUC-KR to UTF-8
-        let result = unsafe {
-            let c_str = CStr::from_ptr(ret);
-            let (decoded, _, had_errors) = encoding_rs::EUC_KR.decode(c_str.to_bytes());
-            if had_errors {
-                return Err(EzTransError::TranslationError(TransErr::EucKrDecodeFailed));
-            }
-            decoded.into_owned()
+        let c_str = unsafe { CStr::from_ptr(ret) };
+        let (decoded, _, had_errors) = encoding_rs::EUC_KR.decode(c_str.to_bytes());
+        let result = if had_errors {
+            Err(EzTransError::TranslationError(TransErr::EucKrDecodeFailed))
+        } else {
+            Ok(decoded.into_owned())
         };
 
-        self.free_memory(ret as *mut c_void)?;
+        // 디코딩에 실패해도 엔진이 할당한 메모리는 해제합니다.
+        unsafe { self.free_memory(ret as *mut c_void)? };
 
-        Ok(result)
+        result
     }
 
-    /// 한글 문자나 특수 문자는 "+x" 또는 "+X" 접두사와 함께 16진수 유니코드 값으로 변환됩니다. 그 외의 문자는 그대로 유지됩니다.
-    /// 왜 필요? 이지트랜스 엔진은 한글 문자나 특수 문자를 처리할 때 문제가 발생할 수 있습니다. 이를 방지하기 위해 문자를 변환한다고 추측하고 있습니다.
-    pub fn hangul_encode(&self, input: &str) -> String {
-        let mut output = String::with_capacity(input.len() * 2);
-
-        for c in input.chars() {
-            if c == '@' || c == '\0' || self.is_hangul_range(c as u32) {
-                write!(&mut output, "+x{:04X}", c as u32).unwrap();
-            } else if self.needs_encoding(c) {
-                write!(&mut output, "+X{:04X}", c as u32).unwrap();
-            } else {
-                output.push(c);
-            }
-        }
-        output
+    /// 메모리를 해제합니다.
+    ///
+    /// # Safety
+    /// `ret`은 이 엔진의 번역 함수가 돌려준, 아직 해제하지 않은 포인터여야 합니다.
+    pub unsafe fn free_memory(&self, ret: *mut c_void) -> Result<(), EzTransError> {
+        self.library.free_mem(ret)
     }
 
-    /// 한글 문자를 판별합니다.
-    pub const fn is_hangul_range(&self, code: u32) -> bool {
-        // Hangul Jamo //한글 자모
-        (code >= 0x1100 && code <= 0x11FF)
-        // Hangul Compatibility Jamo //한글 호환 자모
-        || (code >= 0x3130 && code <= 0x318F)
-        // Hangul Jamo Extended-A //한글 자모 확장-A
-        || (code >= 0xA960 && code <= 0xA97F)
-        // Hangul Syllables //한글 음절
-        || (code >= 0xAC00 && code <= 0xD7A3)
-        // Hangul Jamo Extended-B //한글 자모 확장-B
-        || (code >= 0xD7B0 && code <= 0xD7FF)
+    /// 엔진을 종료합니다. 핸들을 소비하므로 종료한 엔진은 더 이상 사용할 수 없습니다.
+    ///
+    /// 실패해도 엔진은 종료된 것으로 보고 다시 종료하지 않습니다.
+    pub fn terminate(self) -> Result<EzTransLib<Terminated>, EzTransError> {
+        let ret = self.terminate_engine();
+        let terminated = self.into_state();
+        ret.map(|()| terminated)
     }
+}
 
-    /// 이지트랜스 엔진이 처리할 수 없는 문자가 문자열에 들어있는지 확인합니다.
-    fn needs_encoding(&self, c: char) -> bool {
-        SPECIAL_CHARS.contains(&c)
+/// `J2K_InitializeEx`를 호출할지 정합니다. `false`면 `J2K_Initialize`를 호출합니다.
+///
+/// `has_ex`와 `has_plain`은 DLL이 각 함수를 내보내는지(그리고 그 기능이 켜져 있는지),
+/// `explicit_args`는 호출한 쪽이 초기화 키나 설치 폴더를 직접 넘겼는지 여부입니다.
+fn use_initialize_ex(
+    method: InitMethod,
+    has_ex: bool,
+    has_plain: bool,
+    explicit_args: bool,
+) -> Result<bool, EzTransError> {
+    let use_ex = match method {
+        InitMethod::Ex => true,
+        InitMethod::Plain => false,
+        InitMethod::Auto => has_ex || !has_plain,
+    };
+    if !use_ex && explicit_args {
+        return Err(EzTransError::ConfigError(format!(
+            "J2K_Initialize takes no init_str or folder_dir (init_method = {:?})",
+            method
+        )));
     }
+    Ok(use_ex)
+}
 
-    /// "+x" 또는 "+X" 접두사와 함께 16진수 유니코드 값으로 변환된 문자를 원래 문자로 변환합니다.
-    pub fn hangul_decode(&self, input: &str) -> String {
-        let mut output = String::with_capacity(input.len());
-        let mut chars = input.chars().peekable();
-
-        while let Some(ch) = chars.next() {
-            match ch {
-                '+' => match chars.peek() {
-                    Some('x') | Some('X') => {
-                        chars.next(); // Consume 'x' or 'X'
-                        let hex: String = chars.by_ref().take(4).collect();
-                        if hex.len() == 4 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
-                            if let Ok(code) = u32::from_str_radix(&hex, 16) {
-                                if let Some(decoded_char) = std::char::from_u32(code) {
-                                    output.push(decoded_char);
-                                    continue;
-                                }
-                            }
-                        }
-                        output.push('+');
-                        output.push_str(&hex);
-                    }
-                    _ => output.push('+'),
-                },
-                _ => output.push(ch),
-            }
-        }
-
-        output
+/// 초기화 함수는 성공하면 1을 돌려줍니다.
+#[cfg(any(feature = "initialize", feature = "initialize-ex"))]
+fn check_init(entry_point: &'static str, code: c_int) -> Result<(), EzTransError> {
+    if code == 1 {
+        Ok(())
+    } else {
+        Err(EzTransError::InitializationError { entry_point, code })
     }
+}
 
-    /// 메모리를 해제합니다.
-    pub fn free_memory(&self, ret: *mut c_void) -> Result<(), EzTransError> {
-        unsafe { ez_ffi::FREE_MEM.as_ref().map_err(|e| e.clone())?(ret) };
+/// 성공하면 0을 돌려주는 엔진 함수의 반환값을 확인합니다.
+#[cfg(any(
+    feature = "reload-user-dict",
+    feature = "set-del-jpn",
+    feature = "set-field",
+    feature = "set-hnj2han",
+    feature = "set-jwin",
+    feature = "set-prior-dict",
+    feature = "set-property"
+))]
+fn check_call(function: &'static str, code: c_int) -> Result<(), EzTransError> {
+    if code == 0 {
         Ok(())
+    } else {
+        Err(EzTransError::EngineCallFailed { function, code })
     }
+}
 
-    pub fn terminate(&self) -> Result<(), EzTransError> {
-        let terminate = ez_ffi::TERMINATE.as_ref().map_err(|e| e.clone())?;
-        let ret = unsafe { terminate() };
+impl TranslationEngine for EzTransLib<Initialized> {
+    fn translate(&self, input: &str) -> Result<String, EzTransError> {
+        EzTransLib::translate(self, input)
+    }
 
-        if ret == 0 {
-            Ok(())
-        } else {
-            Err(EzTransError::TerminationError)
-        }
+    fn translate_and_encode(&self, input: &str) -> Result<String, EzTransError> {
+        EzTransLib::translate_and_encode(self, input)
+    }
+
+    fn translate_request(
+        &self,
+        input: &str,
+        options: &TranslateOptions,
+    ) -> Result<String, EzTransError> {
+        EzTransLib::translate_request(self, input, options)
+    }
+
+    fn capabilities(&self) -> Option<Capabilities> {
+        Some(EzTransLib::capabilities(self))
+    }
+
+    #[cfg(feature = "stop-translation")]
+    fn stop_handle(&self) -> Option<StopHandle> {
+        self.library.stop_handle().ok()
+    }
+
+    #[cfg(feature = "reload-user-dict")]
+    fn reload_user_dict(&self) -> Result<(), EzTransError> {
+        EzTransLib::reload_user_dict(self)
     }
 }
 
-impl Drop for EzTransLib {
+impl<S: EngineState> Drop for EzTransLib<S> {
     fn drop(&mut self) {
-        let _ = self.terminate();
+        if S::TERMINATE_ON_DROP {
+            let _ = self.terminate_engine();
+        }
     }
 }
 
@@ -247,41 +793,50 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_hangul_encode_decode() {
-        let ez_trans = EzTransLib::new(None).unwrap();
-        ez_trans.initialize(None, None).unwrap();
-        let original = "테스트@漢字㉷";
-        let encoded = ez_trans.hangul_encode(original);
-        println!("encoded: {}", encoded);
-        let decoded = ez_trans.hangul_decode(&encoded);
-        println!("decoded: {}", decoded);
-        assert_eq!(original, decoded);
+    fn test_load_failure_can_be_retried() {
+        for _ in 0..2 {
+            let result = EzTransLib::new(Some("./does-not-exist"));
+            assert!(matches!(result, Err(EzTransError::LibraryLoadError(_))));
+        }
     }
 
     #[test]
-    fn test_hangul_encode() {
-        let ez_trans = EzTransLib::new(None).unwrap();
-        ez_trans.initialize(None, None).unwrap();
-        let input = "Hello@세계";
-        let expected = "Hello+x0040+xC138+xACC4"; // 예시, 실제 인코딩 결과에 맞게 수정
-        let encoded = ez_trans.hangul_encode(input);
-        assert_eq!(encoded, expected);
+    fn test_init_method_selection() {
+        // (has_ex, has_plain) => Auto가 InitializeEx를 쓰는지
+        for (has_ex, has_plain, auto) in [
+            (true, true, true),
+            (true, false, true),
+            (false, true, false),
+            (false, false, true),
+        ] {
+            let choose = |method| use_initialize_ex(method, has_ex, has_plain, false).unwrap();
+            assert_eq!(choose(InitMethod::Auto), auto);
+            assert!(choose(InitMethod::Ex));
+            assert!(!choose(InitMethod::Plain));
+        }
     }
 
     #[test]
-    fn test_hangul_decode() {
-        let ez_trans = EzTransLib::new(None).unwrap();
-        ez_trans.initialize(None, None).unwrap();
-        let input = "Hello+x0040세계";
-        let expected = "Hello@세계";
-        let decoded = ez_trans.hangul_decode(input);
-        assert_eq!(decoded, expected);
+    fn test_explicit_args_are_not_dropped_by_plain_initialize() {
+        assert!(matches!(
+            use_initialize_ex(InitMethod::Plain, true, true, true),
+            Err(EzTransError::ConfigError(_))
+        ));
+        // InitializeEx가 없어서 J2K_Initialize로 대신할 때도 마찬가지입니다.
+        assert!(matches!(
+            use_initialize_ex(InitMethod::Auto, false, true, true),
+            Err(EzTransError::ConfigError(_))
+        ));
+        assert!(use_initialize_ex(InitMethod::Auto, true, true, true).unwrap());
+        assert!(use_initialize_ex(InitMethod::Ex, false, true, true).unwrap());
     }
 
     #[test]
     fn test_translate() {
-        let ez_trans = EzTransLib::new(None).unwrap();
-        ez_trans.initialize(None, None).unwrap();
+        let ez_trans = EzTransLib::new(None)
+            .unwrap()
+            .initialize(None, None)
+            .unwrap();
         let original = "おはようございます。";
         let translated = ez_trans.translate(original).unwrap();
         // 기대하는 번역 결과에 맞게 assert 작성
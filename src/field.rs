@@ -0,0 +1,56 @@
+use std::ffi::c_int;
+use std::fmt;
+
+/// `J2K_SetField`로 선택하는 번역 분야(전문 용어 사전) 번호
+///
+/// 열거형이 아니라 번호를 감싼 타입입니다. ezTrans XP는 분야 번호와 사전의 대응을 공개하지 않았으므로
+/// 이름을 붙이지 않고 번호를 그대로 넘깁니다. 설치된 분야 사전에 맞는 번호를 사용하세요.
+/// 설정 파일에서도 번호(`field = 1`)로 적습니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(from = "c_int", into = "c_int")
+)]
+pub struct Field(c_int);
+
+impl Field {
+    pub const fn new(code: c_int) -> Self {
+        Field(code)
+    }
+
+    /// `J2K_SetField`에 넘기는 번호
+    pub const fn code(self) -> c_int {
+        self.0
+    }
+}
+
+impl From<c_int> for Field {
+    fn from(code: c_int) -> Self {
+        Field(code)
+    }
+}
+
+impl From<Field> for c_int {
+    fn from(field: Field) -> Self {
+        field.code()
+    }
+}
+
+impl fmt::Display for Field {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "분야 {}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_roundtrip() {
+        assert_eq!(Field::from(42), Field::new(42));
+        assert_eq!(c_int::from(Field::new(42)), 42);
+        assert_eq!(Field::new(3).to_string(), "분야 3");
+    }
+}
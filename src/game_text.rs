@@ -0,0 +1,125 @@
+use crate::EzTransError;
+
+/// 게임 문자열의 한 조각
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Segment<'a> {
+    /// 번역할 문장
+    Text(&'a str),
+    /// 그대로 둘 부분 (리치 텍스트 태그, 줄바꿈)
+    Markup(&'a str),
+}
+
+/// Unity 리치 텍스트 태그(`<color=#FF0000>`, `</b>`, `<size=20>` 등)와 줄바꿈을 기준으로 나눕니다.
+///
+/// 태그는 `<` 뒤에 (`/`와) ASCII 영문자가 오고 줄이 바뀌기 전에 `>`로 닫히는 부분입니다.
+/// 줄바꿈은 `\r\n`, `\n`과 게임 스크립트에서 자주 쓰는 두 글자짜리 `\n`입니다.
+pub fn segments(input: &str) -> Vec<Segment<'_>> {
+    let mut segments = Vec::new();
+    let mut text_start = 0;
+    let mut i = 0;
+    while i < input.len() {
+        let rest = &input[i..];
+        let markup_len = tag_len(rest).or_else(|| line_break_len(rest));
+        match markup_len {
+            Some(len) => {
+                if text_start < i {
+                    segments.push(Segment::Text(&input[text_start..i]));
+                }
+                segments.push(Segment::Markup(&rest[..len]));
+                i += len;
+                text_start = i;
+            }
+            None => i += rest.chars().next().map_or(1, char::len_utf8),
+        }
+    }
+    if text_start < input.len() {
+        segments.push(Segment::Text(&input[text_start..]));
+    }
+    segments
+}
+
+fn tag_len(rest: &str) -> Option<usize> {
+    let inner = rest.strip_prefix('<')?;
+    let name = inner.strip_prefix('/').unwrap_or(inner);
+    if !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        return None;
+    }
+    let end = inner.find(['>', '<', '\n'])?;
+    (inner.as_bytes()[end] == b'>').then_some(end + 2)
+}
+
+fn line_break_len(rest: &str) -> Option<usize> {
+    ["\r\n", "\n", "\\n"]
+        .into_iter()
+        .find(|line_break| rest.starts_with(line_break))
+        .map(str::len)
+}
+
+/// 태그와 줄바꿈은 그대로 두고, 그 사이의 문장만 `translate`로 번역해서 다시 이어 붙입니다.
+///
+/// 문장 앞뒤의 공백도 그대로 두며, ASCII만 있는 조각은 엔진에 넘기지 않습니다.
+pub fn translate_game_text(
+    input: &str,
+    mut translate: impl FnMut(&str) -> Result<String, EzTransError>,
+) -> Result<String, EzTransError> {
+    let mut output = String::with_capacity(input.len() * 2);
+    for segment in segments(input) {
+        match segment {
+            Segment::Markup(markup) => output.push_str(markup),
+            Segment::Text(text) => {
+                let core = text.trim();
+                if core.is_ascii() {
+                    output.push_str(text);
+                    continue;
+                }
+                let start = text.len() - text.trim_start().len();
+                output.push_str(&text[..start]);
+                output.push_str(&translate(core)?);
+                output.push_str(&text[start + core.len()..]);
+            }
+        }
+    }
+    Ok(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockEngine, TranslationEngine};
+
+    #[test]
+    fn test_segments() {
+        assert_eq!(
+            segments("<color=#FF0000>はい</color>\\nいいえ"),
+            [
+                Segment::Markup("<color=#FF0000>"),
+                Segment::Text("はい"),
+                Segment::Markup("</color>"),
+                Segment::Markup("\\n"),
+                Segment::Text("いいえ"),
+            ]
+        );
+        // 태그처럼 보이지 않는 꺾쇠는 문장의 일부입니다.
+        assert_eq!(segments("1 < 2 <3>"), [Segment::Text("1 < 2 <3>")]);
+        assert_eq!(
+            segments("<b\nはい"),
+            [
+                Segment::Text("<b"),
+                Segment::Markup("\n"),
+                Segment::Text("はい")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_translate_keeps_markup_and_spacing() {
+        let engine = MockEngine::new();
+        engine.initialize(None, None).unwrap();
+        let translated = translate_game_text(
+            "<b>はい</b>、 <size=20>ありがとう</size>\r\n  Lv.5 ",
+            |text| engine.translate(text),
+        )
+        .unwrap();
+        assert_eq!(translated, "<b>예</b>, <size=20>고마워</size>\r\n  Lv.5 ");
+    }
+}
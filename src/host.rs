@@ -0,0 +1,214 @@
+use std::time::Duration;
+
+use crate::{CancellationToken, DictWatcher, EzTransError, TranslationEngine, Transport};
+
+/// 번역 호스트가 메시지를 읽는 방식
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MessageFormat {
+    /// 메시지가 번역할 문장 그대로입니다. 번역 결과나 `Translation error: ...`를 돌려줍니다.
+    #[default]
+    Raw,
+    /// [`protocol`](crate::protocol)의 JSON 요청과 응답 (`serde` 기능 필요)
+    V1,
+}
+
+/// [`serve`] 설정
+#[derive(Debug, Clone, Default)]
+pub struct HostOptions {
+    pub format: MessageFormat,
+    /// 이 시간 안에 끝나지 않은 번역을 멈추고 에러를 돌려줍니다.
+    pub timeout: Option<Duration>,
+}
+
+/// `transport`로 받은 메시지를 `engine`으로 번역해서 돌려줍니다.
+///
+/// `transport`에서 더 받을 메시지가 없거나, [`MessageFormat::V1`]에서 종료 요청을 받으면 끝납니다.
+/// `watcher`가 있으면 사전 파일이 바뀐 뒤 처음 받은 요청을 번역하기 전에 사용자 사전을 다시 읽습니다.
+pub fn serve<E, T>(
+    engine: &E,
+    transport: &mut T,
+    watcher: Option<&DictWatcher>,
+    options: &HostOptions,
+) -> Result<(), EzTransError>
+where
+    E: TranslationEngine + ?Sized,
+    T: Transport + ?Sized,
+{
+    if options.format == MessageFormat::V1 && !cfg!(feature = "serde") {
+        return Err(EzTransError::FeatureDisabled("serde"));
+    }
+
+    let cancel = CancellationToken::new();
+    while let Some(message) = transport.recv()? {
+        if watcher.is_some_and(DictWatcher::take_changed) {
+            if let Err(e) = engine.reload_user_dict() {
+                eprintln!("Failed to reload user dictionary: {}", e);
+            }
+        }
+
+        let (reply, shutdown) = match options.format {
+            MessageFormat::Raw => {
+                let reply = match engine.translate_cancellable(&message, &cancel, options.timeout) {
+                    Ok(translated) => translated,
+                    Err(error) => format!("Translation error: {}", &error),
+                };
+                (reply, false)
+            }
+            #[cfg(feature = "serde")]
+            MessageFormat::V1 => {
+                let response =
+                    crate::protocol::handle_message(engine, &message, &cancel, options.timeout);
+                (response.to_message(), response.is_shutdown())
+            }
+            #[cfg(not(feature = "serde"))]
+            MessageFormat::V1 => unreachable!("checked above"),
+        };
+        transport.send(&reply)?;
+        if shutdown {
+            break;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{LineTransport, MockEngine, TcpTransport};
+    use std::io::{BufRead, BufReader, Cursor, Write};
+    use std::net::TcpStream;
+    use std::thread;
+
+    fn engine() -> MockEngine {
+        let engine = MockEngine::new();
+        engine.initialize(None, None).unwrap();
+        engine
+    }
+
+    fn serve_lines(input: &str, options: &HostOptions) -> String {
+        let mut transport = LineTransport::new(Cursor::new(input.to_string()), Vec::new());
+        serve(&engine(), &mut transport, None, options).unwrap();
+        String::from_utf8(transport.into_inner().1).unwrap()
+    }
+
+    #[test]
+    fn test_raw_lines() {
+        let output = serve_lines(
+            "おはようございます。\nさようなら。\n",
+            &HostOptions::default(),
+        );
+        assert_eq!(output, "안녕하세요.\n안녕히 가세요.\n");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_v1_stops_at_shutdown() {
+        let input = concat!(
+            r#"{"v":1,"type":"translate","id":1,"text":"はい"}"#,
+            "\n",
+            r#"{"v":1,"type":"shutdown","id":2}"#,
+            "\n",
+            r#"{"v":1,"type":"translate","id":3,"text":"いいえ"}"#,
+            "\n",
+        );
+        let options = HostOptions {
+            format: MessageFormat::V1,
+            ..Default::default()
+        };
+        let output = serve_lines(input, &options);
+        assert_eq!(
+            output,
+            concat!(
+                r#"{"v":1,"type":"translated","id":1,"text":"예"}"#,
+                "\n",
+                r#"{"v":1,"type":"shutting_down","id":2}"#,
+                "\n",
+            )
+        );
+    }
+
+    /// 연결마다 한 줄씩 보내고 답을 읽은 뒤 끊습니다.
+    fn ask<S: std::io::Read + Write>(mut stream: S, line: &str) -> String
+    where
+        for<'a> &'a S: std::io::Read,
+    {
+        writeln!(stream, "{}", line).unwrap();
+        let mut reply = String::new();
+        BufReader::new(&stream).read_line(&mut reply).unwrap();
+        reply.trim_end().to_string()
+    }
+
+    #[test]
+    fn test_tcp_serves_connections_one_after_another() {
+        let mut transport = TcpTransport::bind("127.0.0.1:0").unwrap();
+        let addr = transport.listener().local_addr().unwrap();
+        let client = thread::spawn(move || {
+            let first = ask(TcpStream::connect(addr).unwrap(), "はい");
+            let second = ask(TcpStream::connect(addr).unwrap(), "いいえ");
+            (first, second)
+        });
+
+        // 두 번째 연결에서 한 문장을 받으면 끝나도록 메시지 수를 셉니다.
+        struct Limited<'a>(&'a mut TcpTransport, usize);
+        impl Transport for Limited<'_> {
+            fn recv(&mut self) -> Result<Option<String>, EzTransError> {
+                if self.1 == 0 {
+                    return Ok(None);
+                }
+                self.1 -= 1;
+                self.0.recv()
+            }
+            fn send(&mut self, message: &str) -> Result<(), EzTransError> {
+                self.0.send(message)
+            }
+        }
+        serve(
+            &engine(),
+            &mut Limited(&mut transport, 2),
+            None,
+            &HostOptions::default(),
+        )
+        .unwrap();
+
+        let (first, second) = client.join().unwrap();
+        assert_eq!(first, "예");
+        assert_eq!(second, "아니오");
+    }
+
+    #[cfg(all(unix, feature = "serde"))]
+    #[test]
+    fn test_unix_socket_v1() {
+        use crate::UnixTransport;
+        use std::os::unix::net::UnixStream;
+
+        let path = std::env::temp_dir().join(format!("eztrans-host-{}.sock", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut transport = UnixTransport::bind(&path).unwrap();
+        let client = {
+            let path = path.clone();
+            thread::spawn(move || {
+                let stream = UnixStream::connect(&path).unwrap();
+                let translated = ask(
+                    stream.try_clone().unwrap(),
+                    r#"{"v":1,"type":"translate","id":1,"text":"ありがとう"}"#,
+                );
+                let shutdown = ask(stream, r#"{"v":1,"type":"shutdown","id":2}"#);
+                (translated, shutdown)
+            })
+        };
+
+        let options = HostOptions {
+            format: MessageFormat::V1,
+            ..Default::default()
+        };
+        serve(&engine(), &mut transport, None, &options).unwrap();
+        let (translated, shutdown) = client.join().unwrap();
+        assert_eq!(
+            translated,
+            r#"{"v":1,"type":"translated","id":1,"text":"고마워"}"#
+        );
+        assert_eq!(shutdown, r#"{"v":1,"type":"shutting_down","id":2}"#);
+        std::fs::remove_file(&path).unwrap();
+    }
+}
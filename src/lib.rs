@@ -1,7 +1,44 @@
+mod cancel;
+mod capabilities;
+pub mod codec;
+mod config;
+mod engine;
 mod error;
+#[cfg(feature = "native-engine")]
 mod ez_ffi;
+#[cfg(feature = "native-engine")]
 mod eztranslib;
+mod field;
+mod game_text;
+mod host;
+mod mock;
+mod options;
+pub mod pe;
+mod property;
+mod protect;
+#[cfg(feature = "serde")]
+pub mod protocol;
+#[cfg(feature = "server")]
+pub mod server;
+mod special_chars_table;
+mod transport;
+mod watch;
 
+pub use cancel::*;
+pub use capabilities::*;
+pub use config::*;
+pub use engine::*;
 pub use error::*;
+#[cfg(feature = "native-engine")]
 pub use ez_ffi::*;
+#[cfg(feature = "native-engine")]
 pub use eztranslib::*;
+pub use field::*;
+pub use game_text::*;
+pub use host::*;
+pub use mock::*;
+pub use options::*;
+pub use property::*;
+pub use protect::*;
+pub use transport::*;
+pub use watch::*;
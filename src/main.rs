@@ -1,53 +1,100 @@
 use std::env;
+use std::time::Duration;
 
-use eztrans_sys::{EzTransError, EzTransLib};
-
-use windows_shared_memory::{Client, RecieveMessage};
+use eztrans_sys::{
+    serve, DictWatcher, Endpoint, EzTransConfig, EzTransError, EzTransLib, HostOptions,
+    MessageFormat, MockEngine,
+};
 
 fn main() -> Result<(), EzTransError> {
     let args: Vec<String> = env::args().collect();
     let mut folder_path: Option<&str> = None;
+    let mut config_path: Option<&str> = None;
+    let mut use_mock = false;
+    let mut watch_dict = false;
+    let mut timeout: Option<Duration> = None;
+    let mut format = MessageFormat::Raw;
+    let mut endpoint = Endpoint::SharedMemory;
 
     for arg in args.iter().skip(1) {
         if arg.starts_with("--folder_path=") {
             folder_path = Some(arg.trim_start_matches("--folder_path=").trim_matches('"'));
+        } else if arg.starts_with("--config=") {
+            config_path = Some(arg.trim_start_matches("--config=").trim_matches('"'));
+        } else if arg == "--mock" {
+            use_mock = true;
+        } else if arg == "--watch_dict" {
+            watch_dict = true;
+        } else if arg.starts_with("--timeout_ms=") {
+            let timeout_ms = arg
+                .trim_start_matches("--timeout_ms=")
+                .parse()
+                .map_err(|e| EzTransError::ConfigError(format!("--timeout_ms: {}", e)))?;
+            timeout = Some(Duration::from_millis(timeout_ms));
+        } else if arg.starts_with("--protocol=") {
+            format = match arg.trim_start_matches("--protocol=") {
+                "raw" => MessageFormat::Raw,
+                "v1" => MessageFormat::V1,
+                other => {
+                    return Err(EzTransError::ConfigError(format!(
+                        "--protocol: unknown protocol `{}` (expected raw or v1)",
+                        other
+                    )))
+                }
+            };
+        } else if arg.starts_with("--transport=") {
+            endpoint = arg.trim_start_matches("--transport=").parse()?;
         }
     }
+    if format == MessageFormat::V1 && !cfg!(feature = "serde") {
+        return Err(EzTransError::FeatureDisabled("serde"));
+    }
+    let host = HostOptions { format, timeout };
 
-    let ez_trans = EzTransLib::new(folder_path)?;
-
-    // Initialize EzTransLib
-    ez_trans.initialize(None, folder_path)?;
-
-    let client = Client::new(None).map_err(|e| EzTransError::SharedMemoryError(e.to_string()))?;
-
-    loop {
-        let recieve_server = client.recv_s2c(true);
-
-        if let RecieveMessage::Message(recv_message) = recieve_server {
-            match ez_trans.translate(&recv_message) {
-                Ok(translated) => {
-                    client
-                        .send_c2s(&translated.as_bytes())
-                        .map_err(|e| EzTransError::SharedMemoryError(e.to_string()))?;
-                }
-                Err(error) => {
-                    client
-                        .send_c2s(&format!("Translation error: {}", &error).as_bytes())
-                        .map_err(|e| EzTransError::SharedMemoryError(e.to_string()))?;
-                }
-            }
-        } else if let RecieveMessage::Exit = recieve_server {
-            break;
-        } else if let RecieveMessage::Error = recieve_server {
-            break;
+    if use_mock {
+        let ez_trans = MockEngine::new();
+        ez_trans.initialize(None, folder_path)?;
+        let mut transport = endpoint.open()?;
+        serve(&ez_trans, &mut transport, None, &host)?;
+        ez_trans.terminate()
+    } else {
+        // Initialize EzTransLib
+        let config = load_config(config_path, folder_path)?;
+        if watch_dict && !cfg!(feature = "reload-user-dict") {
+            return Err(EzTransError::FeatureDisabled("reload-user-dict"));
         }
+        let watcher = if watch_dict {
+            let watcher = DictWatcher::new(config.data_dir(), DictWatcher::DEFAULT_INTERVAL)
+                .map_err(|e| EzTransError::ConfigError(format!("{}: {}", config.data_dir(), e)))?;
+            Some(watcher)
+        } else {
+            None
+        };
+        let ez_trans = EzTransLib::with_config(config)?.initialize(None, None)?;
+        let mut transport = endpoint.open()?;
+        serve(&ez_trans, &mut transport, watcher.as_ref(), &host)?;
+        // Terminate EzTransLib
+        ez_trans.terminate()?;
+        Ok(())
     }
+}
 
-    // Terminate EzTransLib
-    ez_trans.terminate()?;
-
-    Ok(())
+/// `--config`로 받은 설정 파일을 읽고, `--folder_path`가 있으면 설치 경로를 덮어씁니다.
+fn load_config(
+    config_path: Option<&str>,
+    folder_path: Option<&str>,
+) -> Result<EzTransConfig, EzTransError> {
+    let mut config = match config_path {
+        #[cfg(feature = "serde")]
+        Some(config_path) => EzTransConfig::from_file(config_path)?,
+        #[cfg(not(feature = "serde"))]
+        Some(_) => return Err(EzTransError::FeatureDisabled("serde")),
+        None => EzTransConfig::default(),
+    };
+    if let Some(folder_path) = folder_path {
+        config.install_path = folder_path.to_string();
+    }
+    Ok(config)
 }
 
 //おはようございます。 example
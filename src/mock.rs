@@ -0,0 +1,521 @@
+use std::cell::Cell;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use crate::engine::with_overrides;
+use crate::{
+    EngineOptions, EzTransError, StopHandle, TransErr, TranslateMode, TranslateOptions,
+    TranslationEngine,
+};
+
+/// 구절 단위 번역 규칙입니다. 가장 긴 구절이 먼저 적용됩니다.
+const PHRASES: &[(&str, &str)] = &[
+    ("おはようございます", "안녕하세요"),
+    ("こんにちは", "안녕하세요"),
+    ("こんばんは", "안녕하세요"),
+    ("さようなら", "안녕히 가세요"),
+    ("ありがとうございます", "감사합니다"),
+    ("ありがとう", "고마워"),
+    ("すみません", "죄송합니다"),
+    ("いいえ", "아니오"),
+    ("はい", "예"),
+    ("日本語", "일본어"),
+    ("翻訳", "번역"),
+    ("私", "나"),
+    ("。", "."),
+    ("、", ","),
+    ("！", "!"),
+    ("？", "?"),
+    ("「", "\""),
+    ("」", "\""),
+];
+
+/// 구절 규칙에 걸리지 않은 가나를 음차할 때 사용하는 테이블입니다. 가타카나는 히라가나로 바꿔서 찾습니다.
+#[rustfmt::skip]
+const KANA: &[(char, &str)] = &[
+    ('あ', "아"), ('い', "이"), ('う', "우"), ('え', "에"), ('お', "오"),
+    ('か', "카"), ('き', "키"), ('く', "쿠"), ('け', "케"), ('こ', "코"),
+    ('が', "가"), ('ぎ', "기"), ('ぐ', "구"), ('げ', "게"), ('ご', "고"),
+    ('さ', "사"), ('し', "시"), ('す', "스"), ('せ', "세"), ('そ', "소"),
+    ('ざ', "자"), ('じ', "지"), ('ず', "즈"), ('ぜ', "제"), ('ぞ', "조"),
+    ('た', "타"), ('ち', "치"), ('つ', "츠"), ('て', "테"), ('と', "토"),
+    ('だ', "다"), ('ぢ', "지"), ('づ', "즈"), ('で', "데"), ('ど', "도"),
+    ('な', "나"), ('に', "니"), ('ぬ', "누"), ('ね', "네"), ('の', "노"),
+    ('は', "하"), ('ひ', "히"), ('ふ', "후"), ('へ', "헤"), ('ほ', "호"),
+    ('ば', "바"), ('び', "비"), ('ぶ', "부"), ('べ', "베"), ('ぼ', "보"),
+    ('ぱ', "파"), ('ぴ', "피"), ('ぷ', "푸"), ('ぺ', "페"), ('ぽ', "포"),
+    ('ま', "마"), ('み', "미"), ('む', "무"), ('め', "메"), ('も', "모"),
+    ('や', "야"), ('ゆ', "유"), ('よ', "요"),
+    ('ら', "라"), ('り', "리"), ('る', "루"), ('れ', "레"), ('ろ', "로"),
+    ('わ', "와"), ('を', "오"), ('ん', "응"),
+    ('ぁ', "아"), ('ぃ', "이"), ('ぅ', "우"), ('ぇ', "에"), ('ぉ', "오"),
+    ('ゃ', "야"), ('ゅ', "유"), ('ょ', "요"), ('っ', ""),
+];
+
+/// DLL 없이 동작하는 결정적(deterministic) 번역 엔진입니다.
+///
+/// 구절 규칙과 가나 음차 테이블로 일본어를 예측 가능한 한국어로 바꾸며,
+/// 규칙에 없는 문자(ASCII, `+xNNNN` 이스케이프 등)는 그대로 통과시킵니다.
+/// 엔진 옵션은 기록만 하며, `del_jpn`이 켜져 있으면 음차할 가나를 결과에서 지웁니다.
+/// 테스트나 Linux CI에서 [`EzTransLib`](crate::EzTransLib) 대신 사용합니다.
+#[derive(Debug, Default)]
+pub struct MockEngine {
+    rules: Vec<(String, String)>,
+    initialized: Cell<bool>,
+    reloads: Cell<usize>,
+    options: Cell<EngineOptions>,
+    /// 이 문자열이 들어 있는 입력은 번역에 실패합니다.
+    fail_on: Option<String>,
+    /// 이 문자열이 들어 있는 입력은 멈추라는 요청을 받을 때까지 번역이 끝나지 않습니다.
+    hang_on: Option<String>,
+    stop: Arc<Mutex<StopState>>,
+}
+
+/// [`StopHandle`]로 받은 멈춤 요청
+#[derive(Debug, Default)]
+struct StopState {
+    /// 번역하는 중인지 여부
+    translating: bool,
+    /// 이번 번역에 멈추라는 요청이 왔는지 여부
+    requested: bool,
+    /// 번역하지 않을 때 도착한 요청 수
+    stray: usize,
+}
+
+impl MockEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 기본 규칙보다 우선 적용되는 구절 규칙을 추가합니다.
+    pub fn with_rule(mut self, from: &str, to: &str) -> Self {
+        self.rules.push((from.to_string(), to.to_string()));
+        self
+    }
+
+    /// `trigger`가 들어 있는 입력을 받으면 [`StopHandle`]로 멈출 때까지 번역이 끝나지 않게 합니다.
+    /// 엔진이 멈추는 상황을 흉내 낼 때 사용합니다.
+    pub fn with_hang(mut self, trigger: &str) -> Self {
+        self.hang_on = Some(trigger.to_string());
+        self
+    }
+
+    /// `trigger`가 들어 있는 입력은 번역에 실패하게 합니다.
+    pub fn with_failure(mut self, trigger: &str) -> Self {
+        self.fail_on = Some(trigger.to_string());
+        self
+    }
+
+    pub fn is_initialized(&self) -> bool {
+        self.initialized.get()
+    }
+
+    /// `reload_user_dict`가 호출된 횟수
+    pub fn reload_count(&self) -> usize {
+        self.reloads.get()
+    }
+
+    /// 번역하지 않을 때(번역 전이나 끝난 뒤) 도착한 멈춤 요청 수. 이런 요청은 다음 번역에 영향을 주지 않습니다.
+    pub fn stray_stops(&self) -> usize {
+        self.stop_state().stray
+    }
+
+    fn stop_state(&self) -> MutexGuard<'_, StopState> {
+        self.stop.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// 이 엔진에 설정한 옵션. 한 번도 설정하지 않은 항목은 `None`입니다.
+    pub fn options(&self) -> EngineOptions {
+        self.options.get()
+    }
+
+    /// `options`에서 `None`이 아닌 항목을 설정합니다. 초기화하기 전에는 실패합니다.
+    pub fn apply_options(&self, options: &EngineOptions) -> Result<(), EzTransError> {
+        if !self.initialized.get() {
+            return Err(EzTransError::EngineCallFailed {
+                function: "MockEngine::apply_options",
+                code: -1,
+            });
+        }
+        let mut current = self.options.get();
+        current.merge(options);
+        self.options.set(current);
+        Ok(())
+    }
+
+    /// 엔진을 초기화합니다. 초기화하기 전에는 번역이 실패합니다.
+    pub fn initialize(
+        &self,
+        _init_str: Option<&str>,
+        _folder_dir: Option<&str>,
+    ) -> Result<(), EzTransError> {
+        self.initialized.set(true);
+        Ok(())
+    }
+
+    /// 엔진을 종료합니다. 초기화되지 않은 엔진을 종료하면 에러를 반환합니다.
+    pub fn terminate(&self) -> Result<(), EzTransError> {
+        if self.initialized.replace(false) {
+            Ok(())
+        } else {
+            Err(EzTransError::TerminationError)
+        }
+    }
+
+    /// `rest`의 앞부분과 일치하는 가장 긴 구절 규칙을 찾습니다.
+    fn longest_phrase(&self, rest: &str) -> Option<(usize, &str)> {
+        // 길이가 같으면 먼저 나온 규칙(사용자 규칙)이 우선합니다.
+        let custom = self
+            .rules
+            .iter()
+            .map(|(from, to)| (from.as_str(), to.as_str()));
+        custom
+            .chain(PHRASES.iter().copied())
+            .filter(|(from, _)| !from.is_empty() && rest.starts_with(from))
+            .fold(None, |best: Option<(usize, &str)>, (from, to)| match best {
+                Some((len, _)) if len >= from.len() => best,
+                _ => Some((from.len(), to)),
+            })
+    }
+
+    fn translate_text(&self, input: &str) -> Result<String, EzTransError> {
+        if !self.initialized.get() {
+            return Err(EzTransError::TranslationError(TransErr::Failed));
+        }
+
+        if self
+            .fail_on
+            .as_deref()
+            .is_some_and(|trigger| input.contains(trigger))
+        {
+            return Err(EzTransError::TranslationError(TransErr::Failed));
+        }
+
+        if self
+            .hang_on
+            .as_deref()
+            .is_some_and(|trigger| input.contains(trigger))
+        {
+            while !self.stop_state().requested {
+                thread::sleep(Duration::from_millis(1));
+            }
+            return Err(EzTransError::TranslationError(TransErr::Failed));
+        }
+
+        let del_jpn = self.options.get().del_jpn == Some(true);
+        let mut output = String::with_capacity(input.len());
+        let mut rest = input;
+        while let Some(c) = rest.chars().next() {
+            if let Some((len, to)) = self.longest_phrase(rest) {
+                output.push_str(to);
+                rest = &rest[len..];
+                continue;
+            }
+            match Self::transliterate(c) {
+                Some(_) if del_jpn => {}
+                Some(to) => output.push_str(to),
+                None => output.push(c),
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+        Ok(output)
+    }
+
+    fn transliterate(c: char) -> Option<&'static str> {
+        // 가타카나(U+30A1..=U+30F6)는 대응하는 히라가나로 바꿔서 찾습니다.
+        let hiragana = match c as u32 {
+            0x30A1..=0x30F6 => char::from_u32(c as u32 - 0x60).unwrap_or(c),
+            _ => c,
+        };
+        KANA.iter().find(|(k, _)| *k == hiragana).map(|(_, v)| *v)
+    }
+}
+
+impl TranslationEngine for MockEngine {
+    fn translate(&self, input: &str) -> Result<String, EzTransError> {
+        self.stop_state().translating = true;
+        let result = self.translate_text(input);
+        let mut stop = self.stop_state();
+        stop.translating = false;
+        stop.requested = false;
+        result
+    }
+
+    /// 엔진 옵션은 [`EzTransLib`](crate::EzTransLib)처럼 이번 번역에만 적용하고 되돌립니다.
+    /// 규칙 테이블 하나로 번역하므로 번역 모드는 [`TranslateMode::Auto`]만 받고, 다른 모드는 [`EzTransError::Unsupported`]를 반환합니다.
+    fn translate_request(
+        &self,
+        input: &str,
+        options: &TranslateOptions,
+    ) -> Result<String, EzTransError> {
+        if let Some(mode) = options.mode.filter(|mode| *mode != TranslateMode::Auto) {
+            return Err(EzTransError::Unsupported(format!("{:?} mode", mode)));
+        }
+        with_overrides(
+            &self.options.get(),
+            &options.engine_options(),
+            |options| self.apply_options(options),
+            || {
+                if options.encode {
+                    self.translate_and_encode(input)
+                } else {
+                    self.translate(input)
+                }
+            },
+        )
+    }
+
+    fn stop_handle(&self) -> Option<StopHandle> {
+        let stop = self.stop.clone();
+        Some(StopHandle::new(move || {
+            let mut stop = stop.lock().unwrap_or_else(PoisonError::into_inner);
+            if stop.translating {
+                stop.requested = true;
+            } else {
+                stop.stray += 1;
+            }
+        }))
+    }
+
+    fn reload_user_dict(&self) -> Result<(), EzTransError> {
+        if !self.initialized.get() {
+            return Err(EzTransError::TranslationError(TransErr::Failed));
+        }
+        self.reloads.set(self.reloads.get() + 1);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AsciiToken, CancellationToken, HexEscape, PrivateUseArea, ProtectionStrategy};
+
+    fn engine() -> MockEngine {
+        let engine = MockEngine::new();
+        engine.initialize(None, None).unwrap();
+        engine
+    }
+
+    #[test]
+    fn test_translate() {
+        let translated = engine().translate("おはようございます。").unwrap();
+        assert_eq!(translated, "안녕하세요.");
+    }
+
+    #[test]
+    fn test_transliterate_kana() {
+        let translated = engine().translate("カタカナとひらがな").unwrap();
+        assert_eq!(translated, "카타카나토히라가나");
+    }
+
+    #[test]
+    fn test_custom_rule_takes_priority() {
+        let engine = MockEngine::new().with_rule("こんにちは", "반가워요");
+        engine.initialize(None, None).unwrap();
+        assert_eq!(engine.translate("こんにちは！").unwrap(), "반가워요!");
+    }
+
+    #[test]
+    fn test_translate_and_encode() {
+        let translated = engine().translate_and_encode("세계@こんにちは").unwrap();
+        assert_eq!(translated, "세계@안녕하세요");
+    }
+
+    #[test]
+    fn test_translate_protected_strategies() {
+        let engine = engine();
+        for strategy in [
+            &HexEscape::new() as &dyn ProtectionStrategy,
+            &PrivateUseArea,
+            &AsciiToken,
+        ] {
+            let translated = engine
+                .translate_protected("세계@こんにちは", strategy)
+                .unwrap();
+            assert_eq!(translated, "세계@안녕하세요");
+        }
+    }
+
+    #[test]
+    fn test_translate_protected_reports_lost_placeholder() {
+        let engine = MockEngine::new().with_rule("ZX0XZ", "");
+        engine.initialize(None, None).unwrap();
+        let err = engine
+            .translate_protected("가はい", &AsciiToken)
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EzTransError::TranslationError(TransErr::PlaceholderLost(lost)) if lost == vec!['가']
+        ));
+    }
+
+    #[test]
+    fn test_lifecycle() {
+        let engine = MockEngine::new();
+        assert!(engine.translate("はい").is_err());
+        engine.initialize(None, None).unwrap();
+        assert_eq!(engine.translate("はい").unwrap(), "예");
+        engine.terminate().unwrap();
+        assert!(engine.terminate().is_err());
+    }
+
+    #[test]
+    fn test_hung_translation_times_out_and_engine_recovers() {
+        let engine = MockEngine::new().with_hang("止まれ");
+        engine.initialize(None, None).unwrap();
+        let cancel = CancellationToken::new();
+
+        let err = engine
+            .translate_cancellable("止まれ", &cancel, Some(Duration::from_millis(20)))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EzTransError::TranslationError(TransErr::TimedOut)
+        ));
+        let translated = engine
+            .translate_cancellable("はい", &cancel, Some(Duration::from_secs(10)))
+            .unwrap();
+        assert_eq!(translated, "예");
+        assert_eq!(engine.stray_stops(), 0);
+    }
+
+    #[test]
+    fn test_stop_after_translation_does_not_affect_next_call() {
+        let engine = MockEngine::new().with_hang("止まれ");
+        engine.initialize(None, None).unwrap();
+        let stop = engine.stop_handle().unwrap();
+
+        assert_eq!(engine.translate("はい").unwrap(), "예");
+        stop.stop();
+        assert_eq!(engine.stray_stops(), 1);
+
+        assert_eq!(engine.translate("はい").unwrap(), "예");
+        // 늦게 도착한 요청이 다음 번역을 멈추지 않았으므로 시간이 지나야 멈춥니다.
+        let started = std::time::Instant::now();
+        let err = engine
+            .translate_cancellable(
+                "止まれ",
+                &CancellationToken::new(),
+                Some(Duration::from_millis(20)),
+            )
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            EzTransError::TranslationError(TransErr::TimedOut)
+        ));
+        assert!(started.elapsed() >= Duration::from_millis(20));
+        assert_eq!(engine.stray_stops(), 1);
+    }
+
+    #[test]
+    fn test_hung_translation_can_be_cancelled() {
+        let engine = MockEngine::new().with_hang("止まれ");
+        engine.initialize(None, None).unwrap();
+        let cancel = CancellationToken::new();
+
+        let canceller = {
+            let cancel = cancel.clone();
+            thread::spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                cancel.cancel();
+            })
+        };
+        let err = engine
+            .translate_cancellable("止まれ", &cancel, None)
+            .unwrap_err();
+        canceller.join().unwrap();
+        assert!(matches!(
+            err,
+            EzTransError::TranslationError(TransErr::Cancelled)
+        ));
+        assert_eq!(engine.stray_stops(), 0);
+    }
+
+    #[test]
+    fn test_lost_sjis_escape_reports_unmappable_offsets() {
+        // 엔진이 `가`의 이스케이프를 지워 버리는 상황을 흉내 냅니다.
+        let engine = MockEngine::new().with_rule("+XAC00", "");
+        engine.initialize(None, None).unwrap();
+        let input = "はい가😀가";
+        let err =
+            crate::engine::translate_sjis_with(input, |text| engine.translate(text)).unwrap_err();
+        assert!(matches!(
+            err,
+            EzTransError::TranslationError(TransErr::Unmappable(chars))
+                if chars == vec![(6, '가'), (13, '가')]
+        ));
+
+        let translated =
+            crate::engine::translate_sjis_with("はい😀", |text| engine.translate(text)).unwrap();
+        assert_eq!(translated, "예😀");
+    }
+
+    #[test]
+    fn test_reload_user_dict() {
+        let engine = MockEngine::new();
+        assert!(engine.reload_user_dict().is_err());
+        engine.initialize(None, None).unwrap();
+        engine.reload_user_dict().unwrap();
+        assert_eq!(engine.reload_count(), 1);
+    }
+
+    #[test]
+    fn test_engine_options_are_overridden_for_one_request() {
+        let engine = engine();
+        engine
+            .apply_options(&EngineOptions {
+                del_jpn: Some(false),
+                ..Default::default()
+            })
+            .unwrap();
+        let options = TranslateOptions {
+            del_jpn: Some(true),
+            ..Default::default()
+        };
+        assert_eq!(
+            engine.translate_request("はいカナ", &options).unwrap(),
+            "예"
+        );
+        assert_eq!(engine.options().del_jpn, Some(false));
+        assert_eq!(engine.translate("はいカナ").unwrap(), "예카나");
+    }
+
+    #[test]
+    fn test_engine_options_are_restored_when_translation_fails() {
+        let engine = MockEngine::new().with_failure("失敗");
+        engine.initialize(None, None).unwrap();
+        let current = EngineOptions {
+            hnj2han: Some(false),
+            del_jpn: Some(false),
+            ..Default::default()
+        };
+        engine.apply_options(&current).unwrap();
+        let options = TranslateOptions {
+            hnj2han: Some(true),
+            del_jpn: Some(true),
+            ..Default::default()
+        };
+        let err = engine.translate_request("失敗", &options).unwrap_err();
+        assert!(matches!(
+            err,
+            EzTransError::TranslationError(TransErr::Failed)
+        ));
+        assert_eq!(engine.options(), current);
+    }
+
+    #[test]
+    fn test_override_of_unset_option_is_refused() {
+        let engine = engine();
+        let options = TranslateOptions {
+            jwin: Some(true),
+            ..Default::default()
+        };
+        assert!(matches!(
+            engine.translate_request("はい", &options),
+            Err(EzTransError::Unsupported(_))
+        ));
+        assert_eq!(engine.options(), EngineOptions::default());
+    }
+}
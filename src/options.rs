@@ -0,0 +1,152 @@
+use crate::{EzTransError, Field, TranslateMode};
+
+/// 엔진 옵션 묶음
+///
+/// `None`인 항목은 바꾸지 않습니다. 설정 파일의 `[options]` 표에 적으면 초기화할 때 적용되고,
+/// [`EzTransLib::translate_with_options`](crate::EzTransLib::translate_with_options)에 넘기면 한 번의 번역에만 적용됩니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct EngineOptions {
+    /// 번역 분야 (`J2K_SetField`)
+    pub field: Option<Field>,
+    /// 한자를 한글로 변환 (`J2K_SetHnj2han`)
+    pub hnj2han: Option<bool>,
+    /// 번역하지 못한 일본어 삭제 (`J2K_SetDelJPN`)
+    pub del_jpn: Option<bool>,
+    /// J-Win 호환 모드 (`J2K_SetJWin`)
+    pub jwin: Option<bool>,
+}
+
+impl EngineOptions {
+    pub fn is_empty(&self) -> bool {
+        *self == EngineOptions::default()
+    }
+
+    /// `other`에 있는 값으로 덮어씁니다.
+    pub fn merge(&mut self, other: &EngineOptions) {
+        self.field = other.field.or(self.field);
+        self.hnj2han = other.hnj2han.or(self.hnj2han);
+        self.del_jpn = other.del_jpn.or(self.del_jpn);
+        self.jwin = other.jwin.or(self.jwin);
+    }
+
+    /// `overrides`를 적용한 뒤 원래대로 되돌리는 데 필요한 값
+    ///
+    /// `overrides`가 바꾸는 항목만 담습니다. 엔진에서 옵션 값을 읽어 올 수 없으므로,
+    /// 현재 값(`self`)을 모르는 항목을 바꾸려고 하면 되돌릴 수 없어 [`EzTransError::Unsupported`]를 반환합니다.
+    pub fn undo_for(&self, overrides: &EngineOptions) -> Result<EngineOptions, EzTransError> {
+        Ok(EngineOptions {
+            field: undo("field", self.field, overrides.field)?,
+            hnj2han: undo("hnj2han", self.hnj2han, overrides.hnj2han)?,
+            del_jpn: undo("del_jpn", self.del_jpn, overrides.del_jpn)?,
+            jwin: undo("jwin", self.jwin, overrides.jwin)?,
+        })
+    }
+}
+
+fn undo<T>(
+    name: &str,
+    current: Option<T>,
+    overridden: Option<T>,
+) -> Result<Option<T>, EzTransError> {
+    match (overridden, current) {
+        (None, _) => Ok(None),
+        (Some(_), Some(current)) => Ok(Some(current)),
+        (Some(_), None) => Err(EzTransError::Unsupported(format!(
+            "overriding `{}` for one request before it has been set on the engine",
+            name
+        ))),
+    }
+}
+
+/// 요청 하나에만 적용하는 번역 설정
+///
+/// `None`인 항목은 엔진 설정을 그대로 사용합니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Deserialize, serde::Serialize),
+    serde(default, deny_unknown_fields)
+)]
+pub struct TranslateOptions {
+    /// 사용할 엔진 함수
+    pub mode: Option<TranslateMode>,
+    /// `true`면 `translate_and_encode`처럼 보호할 문자를 감싸서 번역합니다.
+    pub encode: bool,
+    /// 이 요청의 번역 분야 (`None`이면 엔진의 현재 값을 바꾸지 않음)
+    pub field: Option<Field>,
+    /// 한자를 한글로 변환 (`None`이면 엔진의 현재 값을 바꾸지 않음)
+    pub hnj2han: Option<bool>,
+    /// 번역하지 못한 일본어 삭제 (`None`이면 엔진의 현재 값을 바꾸지 않음)
+    pub del_jpn: Option<bool>,
+    /// J-Win 호환 모드 (`None`이면 엔진의 현재 값을 바꾸지 않음)
+    pub jwin: Option<bool>,
+}
+
+impl TranslateOptions {
+    /// 이 요청에서 바꾸는 엔진 옵션
+    pub fn engine_options(&self) -> EngineOptions {
+        EngineOptions {
+            field: self.field,
+            hnj2han: self.hnj2han,
+            del_jpn: self.del_jpn,
+            jwin: self.jwin,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_undo_only_touches_overridden_options() {
+        let current = EngineOptions {
+            field: Some(Field::new(8)),
+            hnj2han: Some(true),
+            jwin: Some(false),
+            ..Default::default()
+        };
+        let overrides = EngineOptions {
+            hnj2han: Some(false),
+            jwin: Some(true),
+            ..Default::default()
+        };
+        let undo = current.undo_for(&overrides).unwrap();
+        assert_eq!(
+            undo,
+            EngineOptions {
+                hnj2han: Some(true),
+                jwin: Some(false),
+                ..Default::default()
+            }
+        );
+
+        let mut applied = current;
+        applied.merge(&overrides);
+        applied.merge(&undo);
+        assert_eq!(applied.hnj2han, current.hnj2han);
+        assert_eq!(applied.field, Some(Field::new(8)));
+    }
+
+    #[test]
+    fn test_undo_refuses_options_with_unknown_value() {
+        let current = EngineOptions {
+            hnj2han: Some(true),
+            ..Default::default()
+        };
+        let overrides = EngineOptions {
+            hnj2han: Some(false),
+            del_jpn: Some(true),
+            ..Default::default()
+        };
+        assert!(matches!(
+            current.undo_for(&overrides),
+            Err(EzTransError::Unsupported(message)) if message.contains("del_jpn")
+        ));
+    }
+}
@@ -0,0 +1,656 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::{Capabilities, EzTransError, PeError};
+
+const IMAGE_FILE_DLL: u16 = 0x2000;
+const PE32_MAGIC: u16 = 0x10B;
+const PE32_PLUS_MAGIC: u16 = 0x20B;
+const EXPORT_DIRECTORY: usize = 0;
+const RESOURCE_DIRECTORY: usize = 2;
+const RT_VERSION: u32 = 16;
+const VS_FIXEDFILEINFO_SIGNATURE: u32 = 0xFEEF_04BD;
+
+/// PE 헤더의 CPU 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Machine {
+    I386,
+    Amd64,
+    Arm64,
+    Other(u16),
+}
+
+impl Machine {
+    fn from_raw(raw: u16) -> Self {
+        match raw {
+            0x014C => Machine::I386,
+            0x8664 => Machine::Amd64,
+            0xAA64 => Machine::Arm64,
+            raw => Machine::Other(raw),
+        }
+    }
+
+    /// 이 프로세스가 불러올 수 있는 DLL의 CPU 종류. PE를 불러올 수 없는 플랫폼이면 `None`입니다.
+    pub fn current() -> Option<Self> {
+        if !cfg!(windows) {
+            None
+        } else if cfg!(target_arch = "x86") {
+            Some(Machine::I386)
+        } else if cfg!(target_arch = "x86_64") {
+            Some(Machine::Amd64)
+        } else if cfg!(target_arch = "aarch64") {
+            Some(Machine::Arm64)
+        } else {
+            None
+        }
+    }
+}
+
+impl fmt::Display for Machine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Machine::I386 => write!(f, "x86 (32-bit)"),
+            Machine::Amd64 => write!(f, "x86-64 (64-bit)"),
+            Machine::Arm64 => write!(f, "ARM64"),
+            Machine::Other(raw) => write!(f, "unknown machine 0x{:04X}", raw),
+        }
+    }
+}
+
+/// 버전 리소스의 파일 버전 (`major.minor.build.revision`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct FileVersion(pub u16, pub u16, pub u16, pub u16);
+
+impl FileVersion {
+    fn from_raw(ms: u32, ls: u32) -> Self {
+        FileVersion((ms >> 16) as u16, ms as u16, (ls >> 16) as u16, ls as u16)
+    }
+}
+
+impl fmt::Display for FileVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}.{}", self.0, self.1, self.2, self.3)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Section {
+    virtual_address: u32,
+    virtual_size: u32,
+    raw_offset: u32,
+    raw_size: u32,
+}
+
+/// DLL을 불러오지 않고 파일에서 직접 읽은 PE 정보
+///
+/// 헤더와 섹션 표는 [`PeFile::parse`]에서 검사하고, 내보내기 표와 버전 리소스는 필요할 때 읽습니다.
+/// Windows가 아닌 환경에서도 복사해 온 ezTrans XP 설치 폴더를 검사할 수 있습니다.
+pub struct PeFile<'a> {
+    bytes: &'a [u8],
+    machine: Machine,
+    pe32_plus: bool,
+    characteristics: u16,
+    directories: Vec<(u32, u32)>,
+    sections: Vec<Section>,
+}
+
+impl<'a> PeFile<'a> {
+    /// DOS/PE 헤더와 섹션 표를 읽습니다.
+    pub fn parse(bytes: &'a [u8]) -> Result<Self, PeError> {
+        if bytes.get(..2) != Some(b"MZ") {
+            return Err(PeError::NotMz);
+        }
+        let pe_offset = read_u32(bytes, 0x3C, "DOS header")? as usize;
+        if bytes.get(pe_offset..pe_offset.saturating_add(4)) != Some(b"PE\0\0") {
+            return Err(PeError::NotPe);
+        }
+
+        let coff = at(pe_offset, 4, "COFF header")?;
+        let machine = Machine::from_raw(read_u16(bytes, coff, "COFF header")?);
+        let section_count = read_u16(bytes, at(coff, 2, "COFF header")?, "COFF header")? as usize;
+        let optional_size = read_u16(bytes, at(coff, 16, "COFF header")?, "COFF header")? as usize;
+        let characteristics = read_u16(bytes, at(coff, 18, "COFF header")?, "COFF header")?;
+
+        let optional = at(coff, 20, "optional header")?;
+        let pe32_plus = match read_u16(bytes, optional, "optional header")? {
+            PE32_MAGIC => false,
+            PE32_PLUS_MAGIC => true,
+            magic => return Err(PeError::UnknownOptionalHeader(magic)),
+        };
+        let (count_offset, directories_offset) = if pe32_plus { (108, 112) } else { (92, 96) };
+        let directory_count = read_u32(
+            bytes,
+            at(optional, count_offset, "optional header")?,
+            "optional header",
+        )? as usize;
+        let directory_count =
+            directory_count.min((optional_size.saturating_sub(directories_offset)) / 8);
+        let directories = (0..directory_count)
+            .map(|i| {
+                let offset = at(optional, directories_offset + i * 8, "data directories")?;
+                Ok((
+                    read_u32(bytes, offset, "data directories")?,
+                    read_u32(
+                        bytes,
+                        at(offset, 4, "data directories")?,
+                        "data directories",
+                    )?,
+                ))
+            })
+            .collect::<Result<Vec<_>, PeError>>()?;
+
+        let section_table = at(optional, optional_size, "section table")?;
+        let sections = (0..section_count)
+            .map(|i| {
+                let offset = at(section_table, i * 40, "section table")?;
+                let field = |n| read_u32(bytes, at(offset, n, "section table")?, "section table");
+                Ok(Section {
+                    virtual_size: field(8)?,
+                    virtual_address: field(12)?,
+                    raw_size: field(16)?,
+                    raw_offset: field(20)?,
+                })
+            })
+            .collect::<Result<Vec<_>, PeError>>()?;
+
+        Ok(PeFile {
+            bytes,
+            machine,
+            pe32_plus,
+            characteristics,
+            directories,
+            sections,
+        })
+    }
+
+    pub fn machine(&self) -> Machine {
+        self.machine
+    }
+
+    /// PE32+(64비트) 형식인지 여부
+    pub fn is_pe32_plus(&self) -> bool {
+        self.pe32_plus
+    }
+
+    pub fn is_dll(&self) -> bool {
+        self.characteristics & IMAGE_FILE_DLL != 0
+    }
+
+    /// 이름으로 내보낸 함수 목록. 내보내기 표가 없으면 빈 목록입니다.
+    pub fn exports(&self) -> Result<Vec<String>, PeError> {
+        let Some(&(rva, size)) = self.directories.get(EXPORT_DIRECTORY) else {
+            return Ok(Vec::new());
+        };
+        if rva == 0 || size == 0 {
+            return Ok(Vec::new());
+        }
+
+        let directory = self.offset(rva, "export directory")?;
+        let field = |n| {
+            read_u32(
+                self.bytes,
+                at(directory, n, "export directory")?,
+                "export directory",
+            )
+        };
+        let name_count = field(24)? as usize;
+        let names = self.offset(field(32)?, "export names")?;
+        (0..name_count)
+            .map(|i| {
+                let offset = i
+                    .checked_mul(4)
+                    .ok_or(PeError::Truncated("export names"))
+                    .and_then(|n| at(names, n, "export names"))?;
+                let name_rva = read_u32(self.bytes, offset, "export names")?;
+                self.c_str(name_rva, "export names")
+            })
+            .collect()
+    }
+
+    /// 버전 리소스(`VS_FIXEDFILEINFO`)의 파일 버전. 버전 리소스가 없으면 `None`입니다.
+    pub fn file_version(&self) -> Result<Option<FileVersion>, PeError> {
+        let Some(&(rva, size)) = self.directories.get(RESOURCE_DIRECTORY) else {
+            return Ok(None);
+        };
+        if rva == 0 || size == 0 {
+            return Ok(None);
+        }
+
+        let root = self.offset(rva, "resource directory")?;
+        // 종류(RT_VERSION) → 이름 → 언어 순서로 첫 항목을 따라갑니다.
+        let Some(mut entry) = self.resource_entry(root, root, Some(RT_VERSION))? else {
+            return Ok(None);
+        };
+        for _ in 0..2 {
+            let Some(next) = self.resource_entry(root, entry, None)? else {
+                return Ok(None);
+            };
+            entry = next;
+        }
+
+        let data_rva = read_u32(self.bytes, entry, "version resource")?;
+        let data_size = read_u32(
+            self.bytes,
+            at(entry, 4, "version resource")?,
+            "version resource",
+        )? as usize;
+        let data = self.offset(data_rva, "version resource")?;
+        let data = self
+            .bytes
+            .get(data..data.saturating_add(data_size))
+            .ok_or(PeError::Truncated("version resource"))?;
+
+        let Some(fixed) = (0..data.len().saturating_sub(24)).step_by(4).find(|&i| {
+            read_u32(data, i, "version resource").ok() == Some(VS_FIXEDFILEINFO_SIGNATURE)
+        }) else {
+            return Ok(None);
+        };
+        Ok(Some(FileVersion::from_raw(
+            read_u32(data, fixed + 8, "version resource")?,
+            read_u32(data, fixed + 12, "version resource")?,
+        )))
+    }
+
+    /// 내보내기 표로 [`Capabilities`]를 만듭니다.
+    pub fn capabilities(&self, dll_path: &str) -> Result<Capabilities, PeError> {
+        let exports = self.exports()?;
+        Ok(Capabilities::probe(dll_path, |name| {
+            exports.iter().any(|export| export == name)
+        }))
+    }
+
+    /// 이 프로세스에서 불러올 수 있는 DLL인지 확인합니다.
+    pub fn check_loadable(&self) -> Result<(), PeError> {
+        if !self.is_dll() {
+            return Err(PeError::NotDll);
+        }
+        match Machine::current() {
+            Some(current) if current != self.machine => Err(PeError::WrongMachine {
+                dll: self.machine,
+                process: current,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// 리소스 디렉터리 `directory`에서 `id`(없으면 첫 항목)를 찾아 가리키는 위치를 돌려줍니다.
+    fn resource_entry(
+        &self,
+        root: usize,
+        directory: usize,
+        id: Option<u32>,
+    ) -> Result<Option<usize>, PeError> {
+        let field = |offset: usize, n| {
+            at(offset, n, "resource directory")
+                .and_then(|offset| read_u32(self.bytes, offset, "resource directory"))
+        };
+        let count = |n| {
+            at(directory, n, "resource directory")
+                .and_then(|offset| read_u16(self.bytes, offset, "resource directory"))
+        };
+        let (named, ids) = (count(12)? as usize, count(14)? as usize);
+        for i in 0..named + ids {
+            let entry = at(directory, 16 + i * 8, "resource directory")?;
+            let name = field(entry, 0)?;
+            let target = field(entry, 4)?;
+            if id.is_none() || Some(name) == id {
+                return Ok(Some(root.saturating_add((target & 0x7FFF_FFFF) as usize)));
+            }
+        }
+        Ok(None)
+    }
+
+    /// RVA를 파일 오프셋으로 바꿉니다.
+    fn offset(&self, rva: u32, what: &'static str) -> Result<usize, PeError> {
+        self.sections
+            .iter()
+            .find(|section| {
+                let size = section.virtual_size.max(section.raw_size);
+                rva >= section.virtual_address && rva - section.virtual_address < size
+            })
+            .and_then(|section| {
+                ((rva - section.virtual_address) as usize).checked_add(section.raw_offset as usize)
+            })
+            .ok_or(PeError::BadRva { rva, what })
+    }
+
+    fn c_str(&self, rva: u32, what: &'static str) -> Result<String, PeError> {
+        let start = self.offset(rva, what)?;
+        let bytes = self.bytes.get(start..).ok_or(PeError::Truncated(what))?;
+        let len = bytes
+            .iter()
+            .position(|&b| b == 0)
+            .ok_or(PeError::Truncated(what))?;
+        Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
+}
+
+/// 파일에서 읽은 PE 요약. [`PeFile`]과 달리 파일 내용을 붙잡고 있지 않습니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeInfo {
+    pub path: String,
+    pub machine: Machine,
+    pub is_dll: bool,
+    pub exports: Vec<String>,
+    pub file_version: Option<FileVersion>,
+}
+
+impl PeInfo {
+    /// `path`의 PE 파일을 읽습니다.
+    pub fn read(path: impl AsRef<Path>) -> Result<Self, EzTransError> {
+        let path = path.as_ref();
+        let display = path.display().to_string();
+        let bytes = std::fs::read(path).map_err(|e| EzTransError::InvalidDll {
+            path: display.clone(),
+            error: PeError::Io(e.to_string()),
+        })?;
+        Self::parse(&display, &bytes).map_err(|error| EzTransError::InvalidDll {
+            path: display,
+            error,
+        })
+    }
+
+    pub fn parse(path: &str, bytes: &[u8]) -> Result<Self, PeError> {
+        let pe = PeFile::parse(bytes)?;
+        Ok(PeInfo {
+            path: path.to_string(),
+            machine: pe.machine(),
+            is_dll: pe.is_dll(),
+            exports: pe.exports()?,
+            file_version: pe.file_version()?,
+        })
+    }
+
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities::probe(&self.path, |name| {
+            self.exports.iter().any(|export| export == name)
+        })
+    }
+}
+
+/// DLL을 불러오기 전에 헤더를 검사합니다. 파일이 없거나 PE가 아니거나, 이 프로세스와 CPU 종류가 다르면 에러를 반환합니다.
+///
+/// 내보내기 표나 리소스가 깨져 있는 것은 불러오는 데 지장이 없으므로 여기서 검사하지 않습니다.
+/// 파일 전체가 아니라 헤더와 섹션 표까지만 읽습니다.
+pub fn check_dll(path: &str) -> Result<(), EzTransError> {
+    let invalid = |error| EzTransError::InvalidDll {
+        path: path.to_string(),
+        error,
+    };
+    let bytes = read_headers(path).map_err(|e| invalid(PeError::Io(e.to_string())))?;
+    PeFile::parse(&bytes)
+        .and_then(|pe| pe.check_loadable())
+        .map_err(invalid)
+}
+
+/// 파일 앞부분에서 [`PeFile::parse`]가 읽는 DOS/PE 헤더와 섹션 표까지만 읽습니다.
+///
+/// 헤더가 깨져 있으면 읽은 데까지만 돌려주고, 잘린 부분은 [`PeFile::parse`]가 알려 줍니다.
+fn read_headers(path: &str) -> io::Result<Vec<u8>> {
+    let mut file = File::open(path)?;
+    let mut read_prefix = |len: usize| -> io::Result<Vec<u8>> {
+        let mut bytes = Vec::new();
+        file.seek(SeekFrom::Start(0))?;
+        (&mut file).take(len as u64).read_to_end(&mut bytes)?;
+        Ok(bytes)
+    };
+
+    let dos = read_prefix(0x40)?;
+    let Ok(pe_offset) = read_u32(&dos, 0x3C, "DOS header") else {
+        return Ok(dos);
+    };
+    // 오프셋이 넘치면 읽은 데까지만 돌려주고, 어디가 잘못됐는지는 PeFile::parse가 알려 줍니다.
+    let Ok(coff) = at(pe_offset as usize, 4, "COFF header") else {
+        return Ok(dos);
+    };
+    let headers = read_prefix(coff.saturating_add(20))?;
+    let (Ok(section_count), Ok(optional_size)) = (
+        read_u16(&headers, coff.saturating_add(2), "COFF header"),
+        read_u16(&headers, coff.saturating_add(16), "COFF header"),
+    ) else {
+        return Ok(headers);
+    };
+    read_prefix(
+        coff.saturating_add(20)
+            .saturating_add(optional_size as usize)
+            .saturating_add(section_count as usize * 40),
+    )
+}
+
+/// `base + offset`. 넘치면 `what`이 잘린 것으로 봅니다.
+fn at(base: usize, offset: usize, what: &'static str) -> Result<usize, PeError> {
+    base.checked_add(offset).ok_or(PeError::Truncated(what))
+}
+
+fn read_u16(bytes: &[u8], offset: usize, what: &'static str) -> Result<u16, PeError> {
+    bytes
+        .get(offset..offset.saturating_add(2))
+        .map(|b| u16::from_le_bytes([b[0], b[1]]))
+        .ok_or(PeError::Truncated(what))
+}
+
+fn read_u32(bytes: &[u8], offset: usize, what: &'static str) -> Result<u32, PeError> {
+    bytes
+        .get(offset..offset.saturating_add(4))
+        .map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+        .ok_or(PeError::Truncated(what))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECTION_RVA: u32 = 0x1000;
+    const SECTION_OFFSET: usize = 0x200;
+    const RESOURCE_START: usize = 0x400;
+
+    fn put_u16(buf: &mut [u8], offset: usize, value: u16) {
+        buf[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    fn put_u32(buf: &mut [u8], offset: usize, value: u32) {
+        buf[offset..offset + 4].copy_from_slice(&value.to_le_bytes());
+    }
+
+    /// 섹션 하나에 내보내기 표와 버전 리소스를 담은 최소한의 PE를 만듭니다.
+    fn build_pe(
+        machine: u16,
+        pe32_plus: bool,
+        exports: &[&str],
+        version: Option<[u16; 4]>,
+    ) -> Vec<u8> {
+        let mut pe = vec![0u8; SECTION_OFFSET + 0x800];
+        pe[..2].copy_from_slice(b"MZ");
+        put_u32(&mut pe, 0x3C, 0x40);
+        pe[0x40..0x44].copy_from_slice(b"PE\0\0");
+
+        let coff = 0x44;
+        let optional_size = if pe32_plus { 240 } else { 224 };
+        put_u16(&mut pe, coff, machine);
+        put_u16(&mut pe, coff + 2, 1);
+        put_u16(&mut pe, coff + 16, optional_size as u16);
+        put_u16(&mut pe, coff + 18, IMAGE_FILE_DLL | 0x0102);
+
+        let optional = coff + 20;
+        put_u16(
+            &mut pe,
+            optional,
+            if pe32_plus {
+                PE32_PLUS_MAGIC
+            } else {
+                PE32_MAGIC
+            },
+        );
+        let (count_offset, directories) = if pe32_plus { (108, 112) } else { (92, 96) };
+        put_u32(&mut pe, optional + count_offset, 16);
+        let directories = optional + directories;
+
+        let section = optional + optional_size;
+        pe[section..section + 6].copy_from_slice(b".rdata");
+        put_u32(&mut pe, section + 8, 0x800);
+        put_u32(&mut pe, section + 12, SECTION_RVA);
+        put_u32(&mut pe, section + 16, 0x800);
+        put_u32(&mut pe, section + 20, SECTION_OFFSET as u32);
+        let rva = |offset: usize| SECTION_RVA + offset as u32;
+
+        if !exports.is_empty() {
+            // 내보내기 디렉터리(40바이트) 뒤에 이름 RVA 배열과 이름 문자열을 둡니다.
+            let names = 40;
+            let mut strings = names + exports.len() * 4;
+            put_u32(&mut pe, directories, rva(0));
+            put_u32(&mut pe, directories + 4, 40);
+            put_u32(&mut pe, SECTION_OFFSET + 24, exports.len() as u32);
+            put_u32(&mut pe, SECTION_OFFSET + 32, rva(names));
+            for (i, name) in exports.iter().enumerate() {
+                put_u32(&mut pe, SECTION_OFFSET + names + i * 4, rva(strings));
+                let start = SECTION_OFFSET + strings;
+                pe[start..start + name.len()].copy_from_slice(name.as_bytes());
+                strings += name.len() + 1;
+            }
+        }
+
+        if let Some([a, b, c, d]) = version {
+            // 종류 → 이름 → 언어 디렉터리(각 16 + 8바이트)와 데이터 항목, VS_FIXEDFILEINFO
+            let base = SECTION_OFFSET + RESOURCE_START;
+            put_u32(&mut pe, directories + 16, rva(RESOURCE_START));
+            put_u32(&mut pe, directories + 20, 0x100);
+            for (level, id) in [RT_VERSION, 1, 0x412].into_iter().enumerate() {
+                let directory = base + level * 24;
+                put_u16(&mut pe, directory + 14, 1);
+                put_u32(&mut pe, directory + 16, id);
+                let next = (level + 1) * 24;
+                let flag = if level < 2 { 0x8000_0000 } else { 0 };
+                put_u32(&mut pe, directory + 20, next as u32 | flag);
+            }
+            let data_entry = base + 72;
+            let data = RESOURCE_START + 96;
+            put_u32(&mut pe, data_entry, rva(data));
+            put_u32(&mut pe, data_entry + 4, 92);
+            let fixed = SECTION_OFFSET + data + 40;
+            put_u32(&mut pe, fixed, VS_FIXEDFILEINFO_SIGNATURE);
+            put_u32(&mut pe, fixed + 8, (a as u32) << 16 | b as u32);
+            put_u32(&mut pe, fixed + 12, (c as u32) << 16 | d as u32);
+        }
+        pe
+    }
+
+    #[test]
+    fn test_parse_exports_and_version() {
+        let bytes = build_pe(
+            0x014C,
+            false,
+            &["J2K_InitializeEx", "J2K_TranslateMMNT", "J2K_FreeMem"],
+            Some([1, 2, 3, 4]),
+        );
+        let pe = PeFile::parse(&bytes).unwrap();
+        assert_eq!(pe.machine(), Machine::I386);
+        assert!(!pe.is_pe32_plus());
+        assert!(pe.is_dll());
+        assert_eq!(
+            pe.exports().unwrap(),
+            ["J2K_InitializeEx", "J2K_TranslateMMNT", "J2K_FreeMem"]
+        );
+        assert_eq!(pe.file_version().unwrap(), Some(FileVersion(1, 2, 3, 4)));
+
+        let capabilities = pe.capabilities("J2KEngine.dll").unwrap();
+        assert!(capabilities.has_export("J2K_TranslateMMNT"));
+        assert!(!capabilities.ehnd);
+    }
+
+    #[test]
+    fn test_parse_pe32_plus_without_resources() {
+        let bytes = build_pe(0x8664, true, &["J2K_TranslateMMNTW"], None);
+        let info = PeInfo::parse("J2KEngine.dll", &bytes).unwrap();
+        assert_eq!(info.machine, Machine::Amd64);
+        assert_eq!(info.file_version, None);
+        assert!(info.capabilities().ehnd);
+    }
+
+    #[test]
+    fn test_rejects_non_pe() {
+        assert!(matches!(PeFile::parse(b"not a dll"), Err(PeError::NotMz)));
+
+        let mut bytes = build_pe(0x014C, false, &[], None);
+        bytes[0x40] = b'X';
+        assert!(matches!(PeFile::parse(&bytes), Err(PeError::NotPe)));
+
+        let bytes = build_pe(0x014C, false, &["J2K_FreeMem"], None);
+        assert!(matches!(
+            PeFile::parse(&bytes[..0x100]),
+            Err(PeError::Truncated(_))
+        ));
+    }
+
+    #[test]
+    fn test_check_dll_reads_only_headers() {
+        let path = std::env::temp_dir().join(format!("eztrans-pe-{}.dll", std::process::id()));
+        // 섹션 표 바로 뒤에서 잘린 파일도 헤더만 보면 되므로 통과합니다.
+        let bytes = build_pe(0x014C, false, &["J2K_FreeMem"], None);
+        std::fs::write(&path, &bytes[..0x44 + 20 + 224 + 40]).unwrap();
+        let path = path.to_str().unwrap();
+        let result = check_dll(path);
+        std::fs::write(path, &bytes[..0x100]).unwrap();
+        let truncated = check_dll(path);
+        std::fs::remove_file(path).unwrap();
+
+        if Machine::current().is_some_and(|current| current != Machine::I386) {
+            assert!(result.is_err());
+        } else {
+            result.unwrap();
+        }
+        assert!(matches!(
+            truncated,
+            Err(EzTransError::InvalidDll {
+                error: PeError::Truncated(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_overflowing_offsets_are_errors() {
+        let mut bytes = build_pe(0x014C, false, &["J2K_FreeMem"], None);
+        put_u32(&mut bytes, 0x3C, 0xFFFF_FFFF);
+        assert!(matches!(PeFile::parse(&bytes), Err(PeError::NotPe)));
+
+        let path =
+            std::env::temp_dir().join(format!("eztrans-pe-lfanew-{}.dll", std::process::id()));
+        std::fs::write(&path, &bytes).unwrap();
+        let result = check_dll(path.to_str().unwrap());
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            result,
+            Err(EzTransError::InvalidDll {
+                error: PeError::NotPe,
+                ..
+            })
+        ));
+
+        // 섹션이 파일 끝 너머를 가리키면 읽다가 넘치지 않고 에러를 돌려줍니다.
+        let mut bytes = build_pe(0x014C, false, &["J2K_FreeMem"], None);
+        put_u32(&mut bytes, 0x44 + 20 + 224 + 20, u32::MAX - 0x10);
+        let pe = PeFile::parse(&bytes).unwrap();
+        assert!(matches!(
+            pe.exports(),
+            Err(PeError::Truncated(_) | PeError::BadRva { .. })
+        ));
+    }
+
+    #[test]
+    fn test_check_loadable_rejects_other_machine() {
+        let other = if Machine::current() == Some(Machine::I386) {
+            0x8664
+        } else {
+            0x014C
+        };
+        let bytes = build_pe(other, false, &[], None);
+        let result = PeFile::parse(&bytes).unwrap().check_loadable();
+        if Machine::current().is_some() {
+            assert!(matches!(result, Err(PeError::WrongMachine { .. })));
+        } else {
+            assert!(result.is_ok());
+        }
+    }
+}
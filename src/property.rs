@@ -0,0 +1,101 @@
+use std::ffi::c_int;
+
+use crate::EzTransError;
+
+/// `J2K_GetProperty`/`J2K_SetProperty`로 읽고 쓰는 엔진 속성 번호
+///
+/// ezTrans XP는 속성 번호와 그 의미를 공개하지 않았으므로 이름을 붙이지 않고 번호를 그대로 넘깁니다.
+/// 출처를 댈 수 있는 번호가 하나도 없어 이름 붙은 상수도 두지 않습니다. 값도 엔진이 주고받는 정수 그대로 다룹니다.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct EngineProperty(c_int);
+
+impl EngineProperty {
+    pub const fn new(id: c_int) -> Self {
+        EngineProperty(id)
+    }
+
+    /// 엔진에 넘기는 속성 번호
+    pub const fn id(self) -> c_int {
+        self.0
+    }
+}
+
+/// [`snapshot`](crate::EzTransLib::snapshot)으로 저장한 속성 값 목록
+///
+/// 엔진 전체의 속성이 아니라, 저장할 때 넘긴 속성만 담습니다.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct PropertySnapshot {
+    values: Vec<(EngineProperty, c_int)>,
+}
+
+impl PropertySnapshot {
+    /// `properties`의 값을 `get`으로 읽어 저장합니다.
+    ///
+    /// 빈 목록을 저장하면 되돌릴 때 아무것도 하지 않으므로 [`EzTransError::ConfigError`]를 반환합니다.
+    pub fn capture(
+        properties: impl IntoIterator<Item = EngineProperty>,
+        mut get: impl FnMut(EngineProperty) -> Result<c_int, EzTransError>,
+    ) -> Result<Self, EzTransError> {
+        let mut snapshot = PropertySnapshot::default();
+        for property in properties {
+            snapshot.insert(property, get(property)?);
+        }
+        if snapshot.values.is_empty() {
+            return Err(EzTransError::ConfigError(
+                "a property snapshot needs at least one property id".to_string(),
+            ));
+        }
+        Ok(snapshot)
+    }
+
+    pub fn get(&self, property: EngineProperty) -> Option<c_int> {
+        self.values
+            .iter()
+            .find(|(p, _)| *p == property)
+            .map(|(_, value)| *value)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EngineProperty, c_int)> + '_ {
+        self.values.iter().copied()
+    }
+
+    /// 값을 추가하거나 덮어씁니다.
+    pub fn insert(&mut self, property: EngineProperty, value: c_int) {
+        match self.values.iter_mut().find(|(p, _)| *p == property) {
+            Some(entry) => entry.1 = value,
+            None => self.values.push((property, value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_snapshot_insert_overwrites() {
+        let property = EngineProperty::new(9);
+        let mut snapshot = PropertySnapshot::default();
+        snapshot.insert(property, 1);
+        snapshot.insert(property, 0);
+        assert_eq!(snapshot.iter().count(), 1);
+        assert_eq!(snapshot.get(property), Some(0));
+        assert_eq!(snapshot.get(EngineProperty::new(3)), None);
+    }
+
+    #[test]
+    fn test_capture_reads_given_properties_and_rejects_empty_list() {
+        let snapshot = PropertySnapshot::capture(
+            [EngineProperty::new(1), EngineProperty::new(4)],
+            |property| Ok(property.id() * 10),
+        )
+        .unwrap();
+        assert_eq!(snapshot.iter().collect::<Vec<_>>().len(), 2);
+        assert_eq!(snapshot.get(EngineProperty::new(4)), Some(40));
+
+        assert!(matches!(
+            PropertySnapshot::capture([], |_| Ok(0)),
+            Err(EzTransError::ConfigError(_))
+        ));
+    }
+}
@@ -0,0 +1,353 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{codec, TransErr};
+
+/// 보호 대상 문자 하나와, 번역할 때 그 자리에 들어간 자리표시자입니다.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Placeholder {
+    pub token: String,
+    pub original: char,
+}
+
+/// [`ProtectionStrategy::protect`]의 결과입니다.
+#[derive(Debug, Clone, Default)]
+pub struct Protected {
+    /// 엔진에 넘길 문자열
+    pub text: String,
+    /// `text`에 들어간 자리표시자 목록 (등장 순서)
+    pub placeholders: Vec<Placeholder>,
+}
+
+/// 엔진이 망가뜨리는 문자를 번역 전에 자리표시자로 바꾸고, 번역 후 되돌리는 방법입니다.
+pub trait ProtectionStrategy {
+    /// 보호할 문자를 자리표시자로 바꿉니다.
+    fn protect(&self, input: &str) -> Protected;
+
+    /// 번역 결과의 자리표시자를 원래 문자로 되돌립니다.
+    ///
+    /// 번역 중에 사라지거나 깨진 자리표시자가 있으면 [`TransErr::PlaceholderLost`]를 반환합니다.
+    fn restore(&self, translated: &str, protected: &Protected) -> Result<String, TransErr>;
+}
+
+/// 기존 `+xNNNN` 텍스트 이스케이프 ([`codec::hangul_encode`]/[`codec::hangul_decode`])
+///
+/// [`HexEscape::with_extra`]로 기본 목록에 없는 문자를 추가로 이스케이프할 수 있습니다.
+#[derive(Debug, Clone, Default)]
+pub struct HexEscape {
+    extra: HashSet<char>,
+}
+
+impl HexEscape {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 기본 목록에 더해 `extra`의 문자도 `+XNNNN`으로 이스케이프합니다.
+    pub fn with_extra(extra: impl IntoIterator<Item = char>) -> Self {
+        Self {
+            extra: extra.into_iter().collect(),
+        }
+    }
+
+    pub fn extend(&mut self, extra: impl IntoIterator<Item = char>) {
+        self.extra.extend(extra);
+    }
+}
+
+impl ProtectionStrategy for HexEscape {
+    fn protect(&self, input: &str) -> Protected {
+        let text = codec::encode_with(input, |c| {
+            codec::hangul_marker(c).or_else(|| self.extra.contains(&c).then_some('X'))
+        });
+        escape_placeholders(text)
+    }
+
+    fn restore(&self, translated: &str, protected: &Protected) -> Result<String, TransErr> {
+        restore_escapes(translated, protected)
+    }
+}
+
+/// Shift-JIS로 표현할 수 없는 문자만 `+XNNNN` 텍스트 이스케이프로 바꿉니다.
+///
+/// `J2K_TranslateMMNT`는 Shift-JIS 입력만 받으므로, 그 경로에서 문자가 HTML 숫자 참조로 바뀌어
+/// 사라지지 않게 할 때 사용합니다.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShiftJisEscape;
+
+impl ProtectionStrategy for ShiftJisEscape {
+    fn protect(&self, input: &str) -> Protected {
+        escape_placeholders(codec::encode_with(input, |c| {
+            (!codec::is_sjis_mappable(c)).then_some('X')
+        }))
+    }
+
+    fn restore(&self, translated: &str, protected: &Protected) -> Result<String, TransErr> {
+        restore_escapes(translated, protected)
+    }
+}
+
+/// 이스케이프된 문자열에서 자리표시자 목록을 만듭니다.
+/// 인코딩 결과를 디코딩하면 이스케이프된 문자를 순서대로 얻을 수 있습니다.
+fn escape_placeholders(text: String) -> Protected {
+    let mut placeholders = Vec::new();
+    codec::decode_with(&text, |original| {
+        let mut token = String::new();
+        codec::write_escape(&mut token, 'x', original);
+        placeholders.push(Placeholder { token, original });
+    });
+    Protected { text, placeholders }
+}
+
+fn restore_escapes(translated: &str, protected: &Protected) -> Result<String, TransErr> {
+    let mut remaining = count_originals(&protected.placeholders);
+    let restored = codec::decode_with(translated, |c| {
+        if let Some(count) = remaining.get_mut(&c) {
+            *count = count.saturating_sub(1);
+        }
+    });
+    check_remaining(&protected.placeholders, remaining)?;
+    Ok(restored)
+}
+
+/// 보호할 문자를 사용자 정의 영역(Private Use Area) 코드 포인트 하나로 바꿉니다.
+///
+/// 자리표시자가 문자 하나라서 엔진이 쪼개거나 띄어 쓸 수 없습니다. 다만 Shift-JIS로는 표현할 수
+/// 없으므로 Ehnd의 `J2K_TranslateMMNTW` 경로에서만 쓸 수 있습니다.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PrivateUseArea;
+
+impl PrivateUseArea {
+    /// `index`번째 자리표시자 문자. BMP 영역을 다 쓰면 보충 사용자 정의 영역-A를 사용합니다.
+    fn token(index: usize) -> char {
+        const BMP_LEN: usize = 0xF8FF - 0xE000 + 1;
+        let code = if index < BMP_LEN {
+            0xE000 + index as u32
+        } else {
+            0xF0000 + (index - BMP_LEN) as u32
+        };
+        char::from_u32(code).expect("too many distinct protected characters")
+    }
+
+    fn is_private_use(c: char) -> bool {
+        matches!(c as u32, 0xE000..=0xF8FF | 0xF0000..=0xFFFFD | 0x100000..=0x10FFFD)
+    }
+}
+
+impl ProtectionStrategy for PrivateUseArea {
+    fn protect(&self, input: &str) -> Protected {
+        // 원문에 있던 사용자 정의 영역 문자도 보호해야 자리표시자와 섞이지 않습니다.
+        let mut tokens = HashMap::new();
+        let mut protected = Protected::default();
+        for c in input.chars() {
+            if codec::is_protected(c) || Self::is_private_use(c) {
+                let next = tokens.len();
+                let token = *tokens.entry(c).or_insert_with(|| Self::token(next));
+                protected.text.push(token);
+                protected.placeholders.push(Placeholder {
+                    token: token.to_string(),
+                    original: c,
+                });
+            } else {
+                protected.text.push(c);
+            }
+        }
+        protected
+    }
+
+    fn restore(&self, translated: &str, protected: &Protected) -> Result<String, TransErr> {
+        let originals: HashMap<char, char> = protected
+            .placeholders
+            .iter()
+            .filter_map(|p| Some((p.token.chars().next()?, p.original)))
+            .collect();
+        let mut remaining = count_originals(&protected.placeholders);
+
+        let restored = translated
+            .chars()
+            .map(|c| match originals.get(&c) {
+                Some(&original) => {
+                    if let Some(count) = remaining.get_mut(&original) {
+                        *count = count.saturating_sub(1);
+                    }
+                    original
+                }
+                None => c,
+            })
+            .collect();
+        check_remaining(&protected.placeholders, remaining)?;
+        Ok(restored)
+    }
+}
+
+/// 보호할 문자를 `ZX0XZ`처럼 엔진이 그대로 통과시키는 ASCII 영숫자 토큰으로 바꿉니다.
+///
+/// Shift-JIS 경로(`J2K_TranslateMMNT`)에서도 쓸 수 있습니다. 원문에 토큰 구분자가 이미 있으면
+/// 겹치지 않을 때까지 구분자를 늘립니다.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AsciiToken;
+
+impl AsciiToken {
+    fn delimiters(input: &str) -> (String, String) {
+        let mut open = String::from("ZX");
+        let mut close = String::from("XZ");
+        while input.contains(&open) || input.contains(&close) {
+            open.push('Q');
+            close.insert(0, 'Q');
+        }
+        (open, close)
+    }
+}
+
+impl ProtectionStrategy for AsciiToken {
+    fn protect(&self, input: &str) -> Protected {
+        let (open, close) = Self::delimiters(input);
+        let mut tokens: HashMap<char, String> = HashMap::new();
+        let mut protected = Protected::default();
+        for c in input.chars() {
+            if codec::is_protected(c) {
+                let next = tokens.len();
+                let token = tokens
+                    .entry(c)
+                    .or_insert_with(|| format!("{open}{next}{close}"));
+                protected.text.push_str(token);
+                protected.placeholders.push(Placeholder {
+                    token: token.clone(),
+                    original: c,
+                });
+            } else {
+                protected.text.push(c);
+            }
+        }
+        protected
+    }
+
+    fn restore(&self, translated: &str, protected: &Protected) -> Result<String, TransErr> {
+        let originals: HashMap<&str, char> = protected
+            .placeholders
+            .iter()
+            .map(|p| (p.token.as_str(), p.original))
+            .collect();
+        let mut remaining = count_originals(&protected.placeholders);
+
+        let mut restored = translated.to_string();
+        for (token, original) in &originals {
+            let found = restored.matches(token).count();
+            if found > 0 {
+                restored = restored.replace(token, &original.to_string());
+                if let Some(count) = remaining.get_mut(original) {
+                    *count = count.saturating_sub(found);
+                }
+            }
+        }
+        check_remaining(&protected.placeholders, remaining)?;
+        Ok(restored)
+    }
+}
+
+fn count_originals(placeholders: &[Placeholder]) -> HashMap<char, usize> {
+    let mut counts = HashMap::new();
+    for placeholder in placeholders {
+        *counts.entry(placeholder.original).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// 복원되지 못한 자리표시자가 남아 있으면 원래 문자를 등장 순서대로 모아 에러로 반환합니다.
+fn check_remaining(
+    placeholders: &[Placeholder],
+    mut remaining: HashMap<char, usize>,
+) -> Result<(), TransErr> {
+    let mut lost = Vec::new();
+    for placeholder in placeholders.iter().rev() {
+        if let Some(count) = remaining.get_mut(&placeholder.original) {
+            if *count > 0 {
+                *count -= 1;
+                lost.push(placeholder.original);
+            }
+        }
+    }
+    if lost.is_empty() {
+        Ok(())
+    } else {
+        lost.reverse();
+        Err(TransErr::PlaceholderLost(lost))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "세계@こんにちは㉷+x0041";
+
+    fn roundtrip(strategy: &dyn ProtectionStrategy) {
+        let protected = strategy.protect(INPUT);
+        assert!(!protected.text.contains('세'));
+        assert_eq!(
+            strategy.restore(&protected.text, &protected).unwrap(),
+            INPUT
+        );
+    }
+
+    #[test]
+    fn test_strategies_roundtrip() {
+        roundtrip(&HexEscape::new());
+        roundtrip(&ShiftJisEscape);
+        roundtrip(&PrivateUseArea);
+        roundtrip(&AsciiToken);
+    }
+
+    #[test]
+    fn test_hex_escape_reports_split_placeholder() {
+        let protected = HexEscape::new().protect("가나");
+        assert_eq!(protected.text, "+xAC00+xB098");
+        let err = HexEscape::new()
+            .restore("+xAC00 + xB098", &protected)
+            .unwrap_err();
+        assert!(matches!(err, TransErr::PlaceholderLost(lost) if lost == vec!['나']));
+    }
+
+    #[test]
+    fn test_hex_escape_extra_chars() {
+        let escape = HexEscape::with_extra(['★']);
+        let protected = escape.protect("★は☆");
+        assert_eq!(protected.text, "+X2605は☆");
+        assert_eq!(escape.restore(&protected.text, &protected).unwrap(), "★は☆");
+    }
+
+    #[test]
+    fn test_shift_jis_escape_output_is_representable() {
+        let protected = ShiftJisEscape.protect("가おはよう😀");
+        assert!(codec::sjis_unmappable(&protected.text).is_empty());
+        assert_eq!(protected.text, "+XAC00おはよう+XD83D+XDE00");
+    }
+
+    #[test]
+    fn test_private_use_area_keeps_existing_pua_chars() {
+        let input = "\u{E000}가\u{E001}";
+        let protected = PrivateUseArea.protect(input);
+        assert_eq!(protected.text, "\u{E000}\u{E001}\u{E002}");
+        assert_eq!(
+            PrivateUseArea.restore(&protected.text, &protected).unwrap(),
+            input
+        );
+    }
+
+    #[test]
+    fn test_ascii_token_avoids_delimiters_in_input() {
+        let protected = AsciiToken.protect("ZX가");
+        assert_eq!(protected.text, "ZXZXQ0QXZ");
+        assert_eq!(
+            AsciiToken.restore(&protected.text, &protected).unwrap(),
+            "ZX가"
+        );
+    }
+
+    #[test]
+    fn test_ascii_token_reports_lost_placeholder() {
+        let protected = AsciiToken.protect("가 나 가");
+        assert_eq!(protected.text, "ZX0XZ ZX1XZ ZX0XZ");
+        let err = AsciiToken.restore("ZX0XZ ZX 1XZ", &protected).unwrap_err();
+        assert!(matches!(err, TransErr::PlaceholderLost(lost) if lost == vec!['나', '가']));
+    }
+}
@@ -0,0 +1,397 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{
+    run_cancellable, CancellationToken, EzTransError, TransErr, TranslateOptions, TranslationEngine,
+};
+
+/// 번역 호스트가 주고받는 메시지 형식의 버전. 모든 메시지의 `"v"` 필드에 들어갑니다.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// 클라이언트가 보내는 메시지
+///
+/// JSON 객체 하나가 메시지 하나이며, `"type"`으로 종류를 구분합니다.
+///
+/// ```json
+/// {"v":1,"type":"translate","id":1,"text":"おはよう","options":{"mode":"mmnt","encode":true}}
+/// {"v":1,"type":"shutdown","id":2}
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Request {
+    /// `text`를 번역합니다. `options`가 없으면 엔진 설정을 그대로 사용합니다.
+    Translate {
+        id: u64,
+        text: String,
+        #[serde(default)]
+        options: TranslateOptions,
+    },
+    /// 호스트를 종료합니다.
+    Shutdown { id: u64 },
+}
+
+/// 호스트가 돌려주는 메시지. 요청의 `id`를 그대로 돌려줍니다.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Response {
+    Translated {
+        id: u64,
+        text: String,
+    },
+    /// 요청을 처리하지 못했습니다. 요청에서 `id`를 읽지 못했으면 `id`는 `null`입니다.
+    Error {
+        id: Option<u64>,
+        code: ErrorCode,
+        message: String,
+    },
+    /// 종료 요청을 받았습니다. 이 메시지를 보낸 뒤 호스트가 끝납니다.
+    ShuttingDown {
+        id: u64,
+    },
+}
+
+/// [`Response::Error`]의 종류
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// JSON이 아니거나 필드가 잘못된 메시지
+    BadRequest,
+    /// `"v"`가 없거나 지원하지 않는 버전
+    UnsupportedVersion,
+    /// 없는 HTTP 경로
+    NotFound,
+    /// 요청 본문이나 묶음이 설정한 한도보다 큼
+    PayloadTooLarge,
+    /// DLL이나 함수를 불러오지 못함
+    LoadFailed,
+    /// 엔진을 초기화하지 못함
+    InitFailed,
+    /// 엔진이 번역에 실패함
+    TranslationFailed,
+    /// 엔진에 넘길 수 없는 입력 (Shift-JIS로 표현할 수 없는 문자, NUL 등)
+    InvalidInput,
+    /// 보호한 문자가 번역 결과에서 사라짐
+    PlaceholderLost,
+    Cancelled,
+    TimedOut,
+    /// 엔진 설정 함수가 실패했거나 옵션 값이 잘못됨
+    InvalidOption,
+    /// 이 엔진이나 빌드에서 지원하지 않는 번역 모드, 옵션, 기능
+    Unsupported,
+    /// 그 밖의 호스트 에러
+    Internal,
+}
+
+impl From<&EzTransError> for ErrorCode {
+    fn from(error: &EzTransError) -> Self {
+        match error {
+            EzTransError::LibraryLoadError(_)
+            | EzTransError::InvalidDll { .. }
+            | EzTransError::SymbolLoadError(_) => ErrorCode::LoadFailed,
+            EzTransError::InitializationError { .. } => ErrorCode::InitFailed,
+            EzTransError::TranslationError(error) => match error {
+                TransErr::NullPointer | TransErr::Failed | TransErr::EucKrDecodeFailed => {
+                    ErrorCode::TranslationFailed
+                }
+                TransErr::PlaceholderLost(_) => ErrorCode::PlaceholderLost,
+                TransErr::Unmappable(_) => ErrorCode::InvalidInput,
+                TransErr::Cancelled => ErrorCode::Cancelled,
+                TransErr::TimedOut => ErrorCode::TimedOut,
+            },
+            EzTransError::InvalidString(_) | EzTransError::Utf16Error(_) => ErrorCode::InvalidInput,
+            EzTransError::EngineCallFailed { .. } | EzTransError::ConfigError(_) => {
+                ErrorCode::InvalidOption
+            }
+            EzTransError::Unsupported(_) | EzTransError::FeatureDisabled(_) => {
+                ErrorCode::Unsupported
+            }
+            EzTransError::TerminationError
+            | EzTransError::SharedMemoryError(_)
+            | EzTransError::TransportError(_)
+            | EzTransError::EngineThreadError(_) => ErrorCode::Internal,
+        }
+    }
+}
+
+/// 메시지 본문에 `"v"`를 붙인 것
+#[derive(Deserialize, Serialize)]
+struct Envelope<T> {
+    v: u32,
+    #[serde(flatten)]
+    body: T,
+}
+
+impl Request {
+    /// 메시지 하나를 읽습니다. 읽지 못하면 클라이언트에 돌려줄 [`Response::Error`]를 반환합니다.
+    pub fn parse(message: &str) -> Result<Request, Response> {
+        let value: Value = serde_json::from_str(message)
+            .map_err(|e| Response::error(None, ErrorCode::BadRequest, e.to_string()))?;
+        let id = value.get("id").and_then(Value::as_u64);
+        match value.get("v").and_then(Value::as_u64) {
+            Some(v) if v == u64::from(PROTOCOL_VERSION) => {}
+            Some(v) => {
+                return Err(Response::error(
+                    id,
+                    ErrorCode::UnsupportedVersion,
+                    format!(
+                        "unsupported protocol version {}, expected {}",
+                        v, PROTOCOL_VERSION
+                    ),
+                ))
+            }
+            None => {
+                return Err(Response::error(
+                    id,
+                    ErrorCode::UnsupportedVersion,
+                    "missing protocol version \"v\"".to_string(),
+                ))
+            }
+        }
+        serde_json::from_value::<Envelope<Request>>(value)
+            .map(|envelope| envelope.body)
+            .map_err(|e| Response::error(id, ErrorCode::BadRequest, e.to_string()))
+    }
+
+    pub fn id(&self) -> u64 {
+        match self {
+            Request::Translate { id, .. } | Request::Shutdown { id } => *id,
+        }
+    }
+
+    /// `"v"`를 붙인 JSON 문자열
+    pub fn to_message(&self) -> String {
+        to_message(self)
+    }
+}
+
+impl Response {
+    pub fn error(id: Option<u64>, code: ErrorCode, message: String) -> Self {
+        Response::Error { id, code, message }
+    }
+
+    /// 번역 결과를 응답으로 바꿉니다.
+    pub fn from_result(id: u64, result: Result<String, EzTransError>) -> Self {
+        match result {
+            Ok(text) => Response::Translated { id, text },
+            Err(error) => Response::error(Some(id), ErrorCode::from(&error), error.to_string()),
+        }
+    }
+
+    /// 호스트가 이 응답을 보낸 뒤 끝나야 하는지 여부
+    pub fn is_shutdown(&self) -> bool {
+        matches!(self, Response::ShuttingDown { .. })
+    }
+
+    /// 호스트가 보낸 메시지를 읽습니다.
+    pub fn parse(message: &str) -> Result<Response, serde_json::Error> {
+        serde_json::from_str::<Envelope<Response>>(message).map(|envelope| envelope.body)
+    }
+
+    /// `"v"`를 붙인 JSON 문자열
+    pub fn to_message(&self) -> String {
+        to_message(self)
+    }
+}
+
+fn to_message<T: Serialize>(body: &T) -> String {
+    let envelope = Envelope {
+        v: PROTOCOL_VERSION,
+        body,
+    };
+    // 문자열 키만 쓰는 구조체이므로 직렬화는 실패하지 않습니다.
+    serde_json::to_string(&envelope).expect("protocol message is always serializable")
+}
+
+/// 메시지 하나를 처리하고 돌려줄 응답을 만듭니다.
+///
+/// 번역은 [`TranslationEngine::translate_request`]로 하며, `cancel`이나 `timeout`으로 멈출 수 있습니다.
+/// 응답이 [`Response::is_shutdown`]이면 호출한 쪽에서 루프를 끝내야 합니다.
+pub fn handle_message<E: TranslationEngine + ?Sized>(
+    engine: &E,
+    message: &str,
+    cancel: &CancellationToken,
+    timeout: Option<Duration>,
+) -> Response {
+    match Request::parse(message) {
+        Ok(Request::Translate { id, text, options }) => {
+            let result = run_cancellable(engine.stop_handle(), cancel, timeout, || {
+                engine.translate_request(&text, &options)
+            });
+            Response::from_result(id, result)
+        }
+        Ok(Request::Shutdown { id }) => Response::ShuttingDown { id },
+        Err(response) => response,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{MockEngine, TranslateMode};
+
+    fn engine() -> MockEngine {
+        let engine = MockEngine::new();
+        engine.initialize(None, None).unwrap();
+        engine
+    }
+
+    fn handle(engine: &MockEngine, message: &str) -> Response {
+        handle_message(engine, message, &CancellationToken::new(), None)
+    }
+
+    #[test]
+    fn test_request_round_trip() {
+        let request = Request::Translate {
+            id: 7,
+            text: "はい".to_string(),
+            options: TranslateOptions {
+                mode: Some(TranslateMode::MmEx),
+                encode: true,
+                ..Default::default()
+            },
+        };
+        let message = request.to_message();
+        assert!(message.starts_with(r#"{"v":1,"type":"translate","id":7"#));
+        assert_eq!(Request::parse(&message).unwrap(), request);
+    }
+
+    #[test]
+    fn test_translate() {
+        let response = handle(
+            &engine(),
+            r#"{"v":1,"type":"translate","id":1,"text":"세계@こんにちは","options":{"encode":true}}"#,
+        );
+        assert_eq!(
+            response,
+            Response::Translated {
+                id: 1,
+                text: "세계@안녕하세요".to_string()
+            }
+        );
+        assert_eq!(
+            response.to_message(),
+            r#"{"v":1,"type":"translated","id":1,"text":"세계@안녕하세요"}"#
+        );
+    }
+
+    #[test]
+    fn test_shutdown() {
+        let response = handle(&engine(), r#"{"v":1,"type":"shutdown","id":3}"#);
+        assert!(response.is_shutdown());
+        assert_eq!(Response::parse(&response.to_message()).unwrap(), response);
+    }
+
+    #[test]
+    fn test_bad_requests() {
+        let engine = engine();
+        let code = |message| match handle(&engine, message) {
+            Response::Error { id, code, .. } => (id, code),
+            response => panic!("unexpected response {:?}", response),
+        };
+        assert_eq!(code("おはよう"), (None, ErrorCode::BadRequest));
+        assert_eq!(
+            code(r#"{"type":"shutdown","id":1}"#),
+            (Some(1), ErrorCode::UnsupportedVersion)
+        );
+        assert_eq!(
+            code(r#"{"v":2,"type":"shutdown","id":1}"#),
+            (Some(1), ErrorCode::UnsupportedVersion)
+        );
+        assert_eq!(
+            code(r#"{"v":1,"type":"translate","id":2}"#),
+            (Some(2), ErrorCode::BadRequest)
+        );
+        assert_eq!(
+            code(r#"{"v":1,"type":"translate","id":2,"text":"","options":{"bogus":1}}"#),
+            (Some(2), ErrorCode::BadRequest)
+        );
+    }
+
+    #[test]
+    fn test_per_request_options() {
+        let engine = engine();
+        engine
+            .apply_options(&crate::EngineOptions {
+                del_jpn: Some(false),
+                ..Default::default()
+            })
+            .unwrap();
+
+        let response = handle(
+            &engine,
+            r#"{"v":1,"type":"translate","id":1,"text":"はいカナ","options":{"del_jpn":true,"mode":"auto"}}"#,
+        );
+        assert_eq!(
+            response,
+            Response::Translated {
+                id: 1,
+                text: "예".to_string()
+            }
+        );
+        assert_eq!(engine.options().del_jpn, Some(false));
+        let response = handle(
+            &engine,
+            r#"{"v":1,"type":"translate","id":2,"text":"はいカナ"}"#,
+        );
+        assert_eq!(
+            response,
+            Response::Translated {
+                id: 2,
+                text: "예카나".to_string()
+            }
+        );
+    }
+
+    #[test]
+    fn test_unsupported_options_are_refused() {
+        let engine = engine();
+        let code = |message| match handle(&engine, message) {
+            Response::Error { id, code, .. } => (id, code),
+            response => panic!("unexpected response {:?}", response),
+        };
+        // 설정한 적 없는 옵션은 되돌릴 값을 모르므로 바꿀 수 없습니다.
+        assert_eq!(
+            code(r#"{"v":1,"type":"translate","id":1,"text":"はい","options":{"hnj2han":true}}"#),
+            (Some(1), ErrorCode::Unsupported)
+        );
+        assert_eq!(
+            code(r#"{"v":1,"type":"translate","id":2,"text":"はい","options":{"mode":"mmnt"}}"#),
+            (Some(2), ErrorCode::Unsupported)
+        );
+        assert_eq!(engine.options(), crate::EngineOptions::default());
+    }
+
+    #[test]
+    fn test_engine_errors_are_mapped() {
+        let response = handle(
+            &MockEngine::new(),
+            r#"{"v":1,"type":"translate","id":4,"text":"はい"}"#,
+        );
+        assert!(matches!(
+            response,
+            Response::Error {
+                id: Some(4),
+                code: ErrorCode::TranslationFailed,
+                ..
+            }
+        ));
+
+        let engine = MockEngine::new().with_hang("止まれ");
+        engine.initialize(None, None).unwrap();
+        let response = handle_message(
+            &engine,
+            r#"{"v":1,"type":"translate","id":5,"text":"止まれ"}"#,
+            &CancellationToken::new(),
+            Some(Duration::from_millis(20)),
+        );
+        assert!(matches!(
+            response,
+            Response::Error {
+                code: ErrorCode::TimedOut,
+                ..
+            }
+        ));
+    }
+}
@@ -0,0 +1,964 @@
+use std::io::Read;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, Sender};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::ErrorCode;
+use crate::{
+    run_cancellable, translate_game_text, CancellationToken, EzTransError, TranslateMode,
+    TranslateOptions, TranslationEngine,
+};
+
+/// [`Server`] 설정
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServerConfig {
+    /// 연결을 기다릴 주소
+    pub bind: String,
+    /// 요청 본문의 최대 크기 (바이트)
+    pub max_body_bytes: usize,
+    /// 한 요청에 넣을 수 있는 문장 수
+    pub max_batch: usize,
+    /// HTTP 요청을 읽고 응답하는 스레드 수. 번역은 모두 엔진 스레드 하나에서 합니다.
+    pub workers: usize,
+    /// 문장 하나를 번역하는 시간 제한
+    pub timeout: Option<Duration>,
+    /// `POST /shutdown`을 받을지 여부. 요청을 보낼 수 있는 누구나 서버를 끌 수 있으므로 기본값은 `false`입니다.
+    pub allow_shutdown: bool,
+}
+
+impl ServerConfig {
+    pub const DEFAULT_BIND: &'static str = "127.0.0.1:8080";
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            bind: Self::DEFAULT_BIND.to_string(),
+            max_body_bytes: 1024 * 1024,
+            max_batch: 1000,
+            workers: 4,
+            timeout: None,
+            allow_shutdown: false,
+        }
+    }
+}
+
+type Job<E> = Box<dyn FnOnce(&E) + Send>;
+
+fn engine_stopped() -> EzTransError {
+    EzTransError::EngineThreadError("engine thread has stopped".to_string())
+}
+
+/// 엔진을 한 스레드에 두고, 다른 스레드에서 보낸 작업을 순서대로 실행합니다.
+///
+/// [`EzTransLib`](crate::EzTransLib)은 스레드 사이에서 옮길 수 없으므로 엔진은 그 스레드 안에서 만들고 종료합니다.
+/// drop되면 남은 작업을 마치고 `finish`를 호출한 뒤 스레드를 끝냅니다.
+pub struct EngineThread<E: 'static> {
+    jobs: Option<Sender<Job<E>>>,
+    handle: Option<JoinHandle<()>>,
+    alive: Arc<AtomicBool>,
+}
+
+/// drop되면(작업을 모두 마쳤거나 작업 중에 패닉이 나면) 엔진 스레드가 멈췄다고 표시합니다.
+struct AliveGuard(Arc<AtomicBool>);
+
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+impl<E: TranslationEngine + 'static> EngineThread<E> {
+    /// 엔진 스레드를 시작하고 `init`으로 엔진을 만듭니다. `init`이 실패하면 그 에러를 반환합니다.
+    pub fn spawn<I, F>(init: I, finish: F) -> Result<Self, EzTransError>
+    where
+        I: FnOnce() -> Result<E, EzTransError> + Send + 'static,
+        F: FnOnce(E) + Send + 'static,
+    {
+        let (jobs, receiver) = mpsc::channel::<Job<E>>();
+        let (ready, started) = mpsc::channel();
+        let alive = Arc::new(AtomicBool::new(false));
+        let flag = alive.clone();
+        let handle = thread::Builder::new()
+            .name("eztrans-engine".to_string())
+            .spawn(move || {
+                let engine = match init() {
+                    Ok(engine) => engine,
+                    Err(e) => {
+                        let _ = ready.send(Err(e));
+                        return;
+                    }
+                };
+                flag.store(true, Ordering::Release);
+                let guard = AliveGuard(flag);
+                let _ = ready.send(Ok(()));
+                for job in receiver {
+                    job(&engine);
+                }
+                drop(guard);
+                finish(engine);
+            })
+            .map_err(|e| EzTransError::EngineThreadError(e.to_string()))?;
+
+        match started.recv() {
+            Ok(Ok(())) => Ok(EngineThread {
+                jobs: Some(jobs),
+                handle: Some(handle),
+                alive,
+            }),
+            Ok(Err(e)) => {
+                let _ = handle.join();
+                Err(e)
+            }
+            Err(_) => {
+                let _ = handle.join();
+                Err(engine_stopped())
+            }
+        }
+    }
+
+    /// 엔진 스레드가 작업을 받고 있는지 여부. 번역 중이어도 기다리지 않고 바로 답합니다.
+    pub fn is_alive(&self) -> bool {
+        self.alive.load(Ordering::Acquire)
+    }
+
+    /// 엔진 스레드에서 `job`을 실행하고 결과를 기다립니다.
+    pub fn run<T: Send + 'static>(
+        &self,
+        job: impl FnOnce(&E) -> T + Send + 'static,
+    ) -> Result<T, EzTransError> {
+        let (reply, result) = mpsc::channel();
+        self.jobs
+            .as_ref()
+            .ok_or_else(engine_stopped)?
+            .send(Box::new(move |engine| {
+                let _ = reply.send(job(engine));
+            }))
+            .map_err(|_| engine_stopped())?;
+        // 작업 중에 패닉이 나면 스레드가 끝나므로 답을 받지 못합니다.
+        result.recv().map_err(|_| engine_stopped())
+    }
+
+    /// 엔진 스레드에서 `texts`를 차례로 번역합니다.
+    ///
+    /// 엔진 스레드가 멈췄으면 바깥 에러를, 문장 하나가 실패하면 나머지는 번역하지 않고 그 위치를 담은 [`ItemError`]를 반환합니다.
+    pub fn translate(
+        &self,
+        texts: Vec<String>,
+        options: TranslateOptions,
+        timeout: Option<Duration>,
+    ) -> Result<Result<Vec<String>, ItemError>, EzTransError> {
+        self.run(move |engine| {
+            let cancel = CancellationToken::new();
+            texts
+                .iter()
+                .enumerate()
+                .map(|(index, text)| {
+                    run_cancellable(engine.stop_handle(), &cancel, timeout, || {
+                        engine.translate_request(text, &options)
+                    })
+                    .map_err(|error| ItemError { index, error })
+                })
+                .collect()
+        })
+    }
+
+    /// 엔진 스레드에서 게임 문자열 하나를 번역합니다.
+    ///
+    /// 태그와 줄바꿈은 [`translate_game_text`]로 그대로 두고, 문장은 `translate_and_encode`처럼 보호할 문자를 감싸서 번역합니다.
+    pub fn translate_game_text(
+        &self,
+        text: String,
+        timeout: Option<Duration>,
+    ) -> Result<String, EzTransError> {
+        self.run(move |engine| {
+            let options = TranslateOptions {
+                encode: true,
+                ..Default::default()
+            };
+            run_cancellable(
+                engine.stop_handle(),
+                &CancellationToken::new(),
+                timeout,
+                || translate_game_text(&text, |text| engine.translate_request(text, &options)),
+            )
+        })?
+    }
+}
+
+impl<E: 'static> Drop for EngineThread<E> {
+    fn drop(&mut self) {
+        self.jobs.take();
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+/// 여러 문장을 번역하다 실패한 문장의 위치(0부터)와 에러
+#[derive(Debug, Clone)]
+pub struct ItemError {
+    pub index: usize,
+    pub error: EzTransError,
+}
+
+/// HTTP 응답
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reply {
+    pub status: u16,
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+impl Reply {
+    const JSON: &'static str = "application/json; charset=utf-8";
+    const TEXT: &'static str = "text/plain; charset=utf-8";
+
+    pub fn text(status: u16, body: impl Into<String>) -> Self {
+        Reply {
+            status,
+            content_type: Self::TEXT,
+            body: body.into(),
+        }
+    }
+
+    pub fn json(status: u16, body: &impl Serialize) -> Self {
+        Reply {
+            status,
+            content_type: Self::JSON,
+            // 문자열 키만 쓰는 구조체이므로 직렬화는 실패하지 않습니다.
+            body: serde_json::to_string(body).expect("reply is always serializable"),
+        }
+    }
+
+    /// `{"error":{"code":...,"message":...}}`
+    pub fn error(status: u16, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self::error_at(status, code, message.into(), None)
+    }
+
+    /// 여러 문장 중 `index`번째가 실패했으면 `"index"`도 넣습니다.
+    fn error_at(status: u16, code: ErrorCode, message: String, index: Option<usize>) -> Self {
+        #[derive(Serialize)]
+        struct ErrorBody {
+            error: ErrorDetail,
+        }
+        #[derive(Serialize)]
+        struct ErrorDetail {
+            code: ErrorCode,
+            message: String,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            index: Option<usize>,
+        }
+        Reply::json(
+            status,
+            &ErrorBody {
+                error: ErrorDetail {
+                    code,
+                    message,
+                    index,
+                },
+            },
+        )
+    }
+
+    pub fn from_error(error: &EzTransError) -> Self {
+        let code = ErrorCode::from(error);
+        Reply::error(status_for(code), code, error.to_string())
+    }
+
+    /// `{"error":{"code":...,"message":...,"index":...}}`
+    pub fn from_item_error(error: &ItemError) -> Self {
+        let code = ErrorCode::from(&error.error);
+        Reply::error_at(
+            status_for(code),
+            code,
+            error.error.to_string(),
+            Some(error.index),
+        )
+    }
+}
+
+/// 에러 코드에 맞는 HTTP 상태 코드
+pub fn status_for(code: ErrorCode) -> u16 {
+    match code {
+        ErrorCode::BadRequest | ErrorCode::UnsupportedVersion => 400,
+        ErrorCode::NotFound => 404,
+        ErrorCode::PayloadTooLarge => 413,
+        ErrorCode::InvalidInput | ErrorCode::InvalidOption | ErrorCode::PlaceholderLost => 422,
+        ErrorCode::Unsupported => 501,
+        ErrorCode::Cancelled | ErrorCode::LoadFailed | ErrorCode::InitFailed => 503,
+        ErrorCode::TimedOut => 504,
+        ErrorCode::TranslationFailed | ErrorCode::Internal => 500,
+    }
+}
+
+/// 문장 하나 또는 여러 개
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(untagged)]
+pub enum Text {
+    One(String),
+    Batch(Vec<String>),
+}
+
+/// `POST /translate` 본문
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct TranslateBody {
+    pub text: Text,
+    #[serde(default)]
+    pub options: TranslateOptions,
+}
+
+/// Sugoi Translator 오프라인 서버의 요청 (`{"content": ..., "message": "translate sentences"}`)
+///
+/// `content`는 문장 하나 또는 문장 배열이며, 다른 필드는 무시합니다.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct SugoiRequest {
+    #[serde(default)]
+    pub content: Option<Text>,
+    pub message: String,
+}
+
+impl SugoiRequest {
+    /// `content`를 번역해서 배열로 돌려줍니다.
+    pub const TRANSLATE: &'static str = "translate sentences";
+    /// 엔진이 준비되었으면 `true`를 돌려줍니다.
+    pub const CHECK_READY: &'static str = "check if server is ready";
+}
+
+/// `GET /capabilities` 응답
+#[derive(Serialize)]
+struct CapabilitiesBody {
+    dll_path: String,
+    ehnd: bool,
+    exports: Vec<&'static str>,
+    missing_exports: Vec<&'static str>,
+    translate_modes: Vec<TranslateMode>,
+    compiled_features: Vec<&'static str>,
+}
+
+/// 엔진 스레드 하나를 HTTP로 여는 번역 서버
+///
+/// | 요청 | 설명 |
+/// | --- | --- |
+/// | `POST /translate` | `{"text": "..."}` 또는 `{"text": ["...", ...]}`와 선택적인 `options`를 받아 같은 모양으로 돌려줍니다. |
+/// | `GET /health` | 엔진 스레드가 살아 있으면 `{"status":"ok"}` |
+/// | `POST /shutdown` | `{"status":"shutting_down"}`을 돌려주고 [`stop`](Self::stop)처럼 서버를 끝냅니다. [`ServerConfig::allow_shutdown`]을 켜야 합니다. |
+/// | `GET /capabilities` | DLL이 내보내는 함수와 쓸 수 있는 번역 모드 |
+/// | `GET /xunity?from=ja&to=ko&text=...` | XUnity.AutoTranslator의 `CustomTranslate` 엔드포인트. 번역 결과를 그대로 돌려줍니다. |
+/// | `POST /` | Sugoi Translator 오프라인 서버 호환. [`SugoiRequest`]를 받아 번역 결과를 JSON 배열로 돌려줍니다. |
+///
+/// 에러는 `{"error":{"code":...,"message":...}}`이며, `code`는 [`ErrorCode`]입니다.
+/// `/xunity`는 에러도 일반 텍스트로 돌려줍니다.
+pub struct Server<E: 'static> {
+    http: tiny_http::Server,
+    engine: EngineThread<E>,
+    config: ServerConfig,
+    stopping: AtomicBool,
+}
+
+impl<E: TranslationEngine + 'static> Server<E> {
+    /// `config.bind`에서 연결을 기다릴 준비를 합니다.
+    pub fn bind(config: ServerConfig, engine: EngineThread<E>) -> Result<Self, EzTransError> {
+        let http = tiny_http::Server::http(&config.bind)
+            .map_err(|e| EzTransError::TransportError(format!("{}: {}", config.bind, e)))?;
+        Ok(Server {
+            http,
+            engine,
+            config,
+            stopping: AtomicBool::new(false),
+        })
+    }
+
+    /// 실제로 연결을 기다리는 주소. 포트를 0으로 주었을 때 확인할 수 있습니다.
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.http.server_addr().to_ip()
+    }
+
+    pub fn engine(&self) -> &EngineThread<E> {
+        &self.engine
+    }
+
+    /// [`stop`](Self::stop)이 호출될 때까지 요청을 처리합니다.
+    pub fn run(&self) {
+        thread::scope(|scope| {
+            for _ in 0..self.config.workers.max(1) {
+                scope.spawn(|| self.work());
+            }
+        });
+    }
+
+    /// [`run`](Self::run)을 끝냅니다. 처리 중인 요청은 마저 응답합니다.
+    pub fn stop(&self) {
+        self.stopping.store(true, Ordering::Release);
+        for _ in 0..self.config.workers.max(1) {
+            self.http.unblock();
+        }
+    }
+
+    fn work(&self) {
+        while !self.stopping.load(Ordering::Acquire) {
+            // `stop`이 깨운 경우와 일시적인 accept 에러 모두 위에서 다시 확인합니다.
+            if let Ok(request) = self.http.recv() {
+                self.respond(request);
+            }
+        }
+    }
+
+    fn respond(&self, mut request: tiny_http::Request) {
+        let reply = match self.read_body(&mut request) {
+            Ok(body) => self.handle(request.method().as_str(), request.url(), &body),
+            Err(reply) => reply,
+        };
+        let header = tiny_http::Header::from_bytes("Content-Type", reply.content_type)
+            .expect("content type is a valid header");
+        let response = tiny_http::Response::from_string(reply.body)
+            .with_status_code(reply.status)
+            .with_header(header);
+        // 클라이언트가 먼저 끊었으면 보낼 곳이 없습니다.
+        let _ = request.respond(response);
+    }
+
+    fn read_body(&self, request: &mut tiny_http::Request) -> Result<Vec<u8>, Reply> {
+        let limit = self.config.max_body_bytes;
+        let too_large = || {
+            Reply::error(
+                413,
+                ErrorCode::PayloadTooLarge,
+                format!("request body is larger than {} bytes", limit),
+            )
+        };
+        if request.body_length().is_some_and(|length| length > limit) {
+            return Err(too_large());
+        }
+        let mut body = Vec::new();
+        request
+            .as_reader()
+            .take(limit as u64 + 1)
+            .read_to_end(&mut body)
+            .map_err(|e| Reply::error(400, ErrorCode::BadRequest, e.to_string()))?;
+        if body.len() > limit {
+            return Err(too_large());
+        }
+        Ok(body)
+    }
+
+    /// 요청 하나를 처리합니다. `url`에는 쿼리 문자열이 붙어 있을 수 있습니다.
+    pub fn handle(&self, method: &str, url: &str, body: &[u8]) -> Reply {
+        let path = url.split_once('?').map_or(url, |(path, _)| path);
+        match (method, path) {
+            // 끄지 못하게 한 서버에는 이 경로가 없습니다.
+            (_, "/shutdown") if !self.config.allow_shutdown => {
+                Reply::error(404, ErrorCode::NotFound, format!("no route for {}", path))
+            }
+            ("POST", "/translate") => self.translate(body),
+            ("GET", "/health") => self.health(),
+            ("GET", "/capabilities") => self.capabilities(),
+            ("POST", "/shutdown") => self.shutdown(),
+            ("POST", "/") => self.sugoi(body),
+            ("GET", "/xunity") => self.xunity(url.split_once('?').map_or("", |(_, query)| query)),
+            (_, "/" | "/translate" | "/health" | "/capabilities" | "/shutdown" | "/xunity") => {
+                Reply::error(
+                    405,
+                    ErrorCode::BadRequest,
+                    format!("method {} is not allowed on {}", method, path),
+                )
+            }
+            _ => Reply::error(404, ErrorCode::NotFound, format!("no route for {}", path)),
+        }
+    }
+
+    fn translate(&self, body: &[u8]) -> Reply {
+        let request: TranslateBody = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return Reply::error(400, ErrorCode::BadRequest, e.to_string()),
+        };
+        let (texts, batch) = match request.text {
+            Text::One(text) => (vec![text], false),
+            Text::Batch(texts) => (texts, true),
+        };
+        if let Some(reply) = self.check_batch(texts.len()) {
+            return reply;
+        }
+
+        match self
+            .engine
+            .translate(texts, request.options, self.config.timeout)
+        {
+            Ok(Ok(mut translated)) => {
+                let text = if batch {
+                    Text::Batch(translated)
+                } else {
+                    Text::One(translated.pop().unwrap_or_default())
+                };
+                Reply::json(200, &serde_json::json!({ "text": text }))
+            }
+            Ok(Err(e)) if batch => Reply::from_item_error(&e),
+            Ok(Err(e)) => Reply::from_error(&e.error),
+            Err(e) => Reply::from_error(&e),
+        }
+    }
+
+    fn check_batch(&self, len: usize) -> Option<Reply> {
+        (len > self.config.max_batch).then(|| {
+            Reply::error(
+                413,
+                ErrorCode::PayloadTooLarge,
+                format!("batch has more than {} texts", self.config.max_batch),
+            )
+        })
+    }
+
+    /// Sugoi 서버는 문장 하나를 받아도 배열로 돌려줍니다.
+    fn sugoi(&self, body: &[u8]) -> Reply {
+        let request: SugoiRequest = match serde_json::from_slice(body) {
+            Ok(request) => request,
+            Err(e) => return Reply::error(400, ErrorCode::BadRequest, e.to_string()),
+        };
+        match request.message.as_str() {
+            SugoiRequest::TRANSLATE => {}
+            SugoiRequest::CHECK_READY => {
+                return Reply::json(200, &self.engine.is_alive());
+            }
+            message => {
+                return Reply::error(
+                    400,
+                    ErrorCode::BadRequest,
+                    format!("unknown message `{}`", message),
+                )
+            }
+        }
+        let texts = match request.content {
+            Some(Text::One(text)) => vec![text],
+            Some(Text::Batch(texts)) => texts,
+            None => return Reply::error(400, ErrorCode::BadRequest, "missing content"),
+        };
+        if let Some(reply) = self.check_batch(texts.len()) {
+            return reply;
+        }
+
+        let options = TranslateOptions {
+            encode: true,
+            ..Default::default()
+        };
+        match self.engine.translate(texts, options, self.config.timeout) {
+            Ok(Ok(translated)) => Reply::json(200, &translated),
+            Ok(Err(e)) => Reply::from_item_error(&e),
+            Err(e) => Reply::from_error(&e),
+        }
+    }
+
+    /// XUnity.AutoTranslator는 200이 아닌 응답을 번역 실패로 처리하고, 본문을 그대로 로그에 남깁니다.
+    fn xunity(&self, query: &str) -> Reply {
+        let params = match parse_query(query) {
+            Some(params) => params,
+            None => return Reply::text(400, "query string is not valid UTF-8"),
+        };
+        let param = |name: &str| {
+            params
+                .iter()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.as_str())
+        };
+        for (name, expected) in [("from", "ja"), ("to", "ko")] {
+            if let Some(language) = param(name) {
+                if !language.eq_ignore_ascii_case(expected) {
+                    return Reply::text(
+                        400,
+                        format!(
+                            "unsupported language {}={}, expected {}",
+                            name, language, expected
+                        ),
+                    );
+                }
+            }
+        }
+        let Some(text) = param("text") else {
+            return Reply::text(400, "missing text parameter");
+        };
+
+        match self
+            .engine
+            .translate_game_text(text.to_string(), self.config.timeout)
+        {
+            Ok(translated) => Reply::text(200, translated),
+            Err(e) => Reply::text(status_for(ErrorCode::from(&e)), e.to_string()),
+        }
+    }
+
+    /// 작업 대기열을 거치지 않으므로 긴 번역이 진행 중이어도 바로 답합니다.
+    fn health(&self) -> Reply {
+        if self.engine.is_alive() {
+            Reply::json(200, &serde_json::json!({ "status": "ok" }))
+        } else {
+            Reply::from_error(&engine_stopped())
+        }
+    }
+
+    /// 엔진은 [`run`](Self::run)이 끝난 뒤 서버와 함께 drop되면서 종료됩니다.
+    fn shutdown(&self) -> Reply {
+        self.stop();
+        Reply::json(200, &serde_json::json!({ "status": "shutting_down" }))
+    }
+
+    fn capabilities(&self) -> Reply {
+        let capabilities = self.engine.run(|engine| {
+            engine.capabilities().map(|capabilities| CapabilitiesBody {
+                missing_exports: capabilities.missing_exports(),
+                translate_modes: capabilities.translate_modes(),
+                dll_path: capabilities.dll_path,
+                ehnd: capabilities.ehnd,
+                exports: capabilities.exports,
+                compiled_features: capabilities.compiled_features,
+            })
+        });
+        match capabilities {
+            Ok(Some(capabilities)) => Reply::json(200, &capabilities),
+            Ok(None) => Reply::error(
+                501,
+                ErrorCode::Unsupported,
+                "this engine does not report capabilities",
+            ),
+            Err(e) => Reply::from_error(&e),
+        }
+    }
+}
+
+/// `a=1&b=%E3%81%82` 형식의 쿼리 문자열을 풉니다. `+`는 공백으로 읽습니다.
+fn parse_query(query: &str) -> Option<Vec<(String, String)>> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            Some((percent_decode(key)?, percent_decode(value)?))
+        })
+        .collect()
+}
+
+fn percent_decode(s: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.as_bytes();
+    while let Some((&b, tail)) = rest.split_first() {
+        let decoded = match b {
+            b'+' => b' ',
+            b'%' => {
+                let hex = tail.get(..2).and_then(|hex| std::str::from_utf8(hex).ok());
+                match hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    Some(decoded) => {
+                        rest = &tail[2..];
+                        bytes.push(decoded);
+                        continue;
+                    }
+                    // 잘못된 이스케이프는 글자 그대로 둡니다.
+                    None => b'%',
+                }
+            }
+            _ => b,
+        };
+        bytes.push(decoded);
+        rest = tail;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::MockEngine;
+    use std::io::Write;
+    use std::net::TcpStream;
+    use std::time::Instant;
+
+    fn server(config: ServerConfig) -> Server<MockEngine> {
+        let engine = EngineThread::spawn(
+            || {
+                let engine = MockEngine::new().with_hang("止まれ");
+                engine.initialize(None, None)?;
+                Ok(engine)
+            },
+            |engine| engine.terminate().unwrap(),
+        )
+        .unwrap();
+        let config = ServerConfig {
+            bind: "127.0.0.1:0".to_string(),
+            ..config
+        };
+        Server::bind(config, engine).unwrap()
+    }
+
+    fn body(reply: &Reply) -> serde_json::Value {
+        serde_json::from_str(&reply.body).unwrap()
+    }
+
+    #[test]
+    fn test_translate_single_and_batch() {
+        let server = server(ServerConfig::default());
+        let reply = server.handle("POST", "/translate", r#"{"text":"はい"}"#.as_bytes());
+        assert_eq!(reply.status, 200);
+        assert_eq!(body(&reply), serde_json::json!({ "text": "예" }));
+
+        let reply = server.handle(
+            "POST",
+            "/translate",
+            r#"{"text":["おはようございます。","세계@いいえ"],"options":{"encode":true}}"#
+                .as_bytes(),
+        );
+        assert_eq!(
+            body(&reply),
+            serde_json::json!({ "text": ["안녕하세요.", "세계@아니오"] })
+        );
+    }
+
+    #[test]
+    fn test_errors_are_json() {
+        let server = server(ServerConfig {
+            max_batch: 1,
+            timeout: Some(Duration::from_millis(20)),
+            ..Default::default()
+        });
+        let code = |method, url, request: &str| {
+            let reply = server.handle(method, url, request.as_bytes());
+            (reply.status, body(&reply)["error"]["code"].clone())
+        };
+        assert_eq!(
+            code("POST", "/translate", "はい"),
+            (400, "bad_request".into())
+        );
+        assert_eq!(
+            code("POST", "/translate", r#"{"text":["はい","いいえ"]}"#),
+            (413, "payload_too_large".into())
+        );
+        assert_eq!(
+            code("POST", "/translate", r#"{"text":"止まれ"}"#),
+            (504, "timed_out".into())
+        );
+        assert!(
+            body(&server.handle("POST", "/translate", r#"{"text":"止まれ"}"#.as_bytes()))["error"]
+                .get("index")
+                .is_none()
+        );
+        assert_eq!(code("GET", "/translate", ""), (405, "bad_request".into()));
+        assert_eq!(code("GET", "/nope", ""), (404, "not_found".into()));
+        assert_eq!(
+            code("GET", "/capabilities", ""),
+            (501, "unsupported".into())
+        );
+    }
+
+    #[test]
+    fn test_batch_error_reports_failing_index() {
+        let server = server(ServerConfig {
+            timeout: Some(Duration::from_millis(20)),
+            ..Default::default()
+        });
+        let reply = server.handle(
+            "POST",
+            "/translate",
+            r#"{"text":["はい","止まれ","いいえ"]}"#.as_bytes(),
+        );
+        assert_eq!(reply.status, 504);
+        assert_eq!(body(&reply)["error"]["code"], "timed_out");
+        assert_eq!(body(&reply)["error"]["index"], 1);
+
+        let reply = server.handle(
+            "POST",
+            "/",
+            r#"{"content":["止まれ"],"message":"translate sentences"}"#.as_bytes(),
+        );
+        assert_eq!(body(&reply)["error"]["index"], 0);
+    }
+
+    #[test]
+    fn test_health_does_not_wait_for_translation() {
+        let server = server(ServerConfig {
+            timeout: Some(Duration::from_millis(500)),
+            ..Default::default()
+        });
+        thread::scope(|scope| {
+            let translating = scope
+                .spawn(|| server.handle("POST", "/translate", r#"{"text":"止まれ"}"#.as_bytes()));
+            thread::sleep(Duration::from_millis(50));
+            let started = Instant::now();
+            let reply = server.handle("GET", "/health", &[]);
+            assert!(started.elapsed() < Duration::from_millis(250));
+            assert_eq!(body(&reply), serde_json::json!({ "status": "ok" }));
+            assert_eq!(translating.join().unwrap().status, 504);
+        });
+
+        // 작업 중에 패닉이 나서 엔진 스레드가 끝나면 알려 줍니다.
+        assert!(server
+            .engine()
+            .run(|_| -> () { panic!("engine thread panicked") })
+            .is_err());
+        // 답을 받지 못한 뒤에도 스레드가 마저 풀리는 동안은 살아 있다고 나올 수 있습니다.
+        let started = Instant::now();
+        while server.engine().is_alive() && started.elapsed() < Duration::from_secs(5) {
+            thread::sleep(Duration::from_millis(1));
+        }
+        assert!(!server.engine().is_alive());
+        let reply = server.handle("GET", "/health", &[]);
+        assert_eq!(reply.status, 500);
+        assert_eq!(body(&reply)["error"]["code"], "internal");
+    }
+
+    #[test]
+    fn test_sugoi() {
+        let server = server(ServerConfig {
+            max_batch: 2,
+            ..Default::default()
+        });
+        let sugoi = |request: &str| server.handle("POST", "/", request.as_bytes());
+
+        let reply = sugoi(r#"{"content":"おはようございます。","message":"translate sentences"}"#);
+        assert_eq!(reply.status, 200);
+        assert_eq!(body(&reply), serde_json::json!(["안녕하세요."]));
+
+        let reply = sugoi(
+            r#"{"content":["はい","세계@いいえ"],"message":"translate sentences","batch":true}"#,
+        );
+        assert_eq!(body(&reply), serde_json::json!(["예", "세계@아니오"]));
+
+        let reply = sugoi(r#"{"message":"check if server is ready"}"#);
+        assert_eq!(body(&reply), serde_json::json!(true));
+
+        assert_eq!(
+            sugoi(r#"{"content":["はい","はい","はい"],"message":"translate sentences"}"#).status,
+            413
+        );
+        assert_eq!(sugoi(r#"{"content":"はい","message":"close"}"#).status, 400);
+        assert_eq!(sugoi(r#"{"message":"translate sentences"}"#).status, 400);
+    }
+
+    #[test]
+    fn test_shutdown_ends_run_and_finishes_engine() {
+        let finished = Arc::new(AtomicBool::new(false));
+        let engine = {
+            let finished = finished.clone();
+            EngineThread::spawn(
+                || {
+                    let engine = MockEngine::new();
+                    engine.initialize(None, None)?;
+                    Ok(engine)
+                },
+                move |engine| {
+                    engine.terminate().unwrap();
+                    finished.store(true, Ordering::Release);
+                },
+            )
+            .unwrap()
+        };
+        let config = ServerConfig {
+            bind: "127.0.0.1:0".to_string(),
+            allow_shutdown: true,
+            ..Default::default()
+        };
+        let server = Server::bind(config, engine).unwrap();
+
+        let reply = server.handle("POST", "/shutdown", &[]);
+        assert_eq!(
+            body(&reply),
+            serde_json::json!({ "status": "shutting_down" })
+        );
+        assert_eq!(server.handle("GET", "/shutdown", &[]).status, 405);
+        // 이미 멈추라고 했으므로 바로 끝납니다.
+        server.run();
+        assert!(!finished.load(Ordering::Acquire));
+        drop(server);
+        assert!(finished.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn test_shutdown_is_disabled_by_default() {
+        let server = server(ServerConfig::default());
+        let reply = server.handle("POST", "/shutdown", &[]);
+        assert_eq!(reply.status, 404);
+        assert_eq!(body(&reply)["error"]["code"], "not_found");
+        assert_eq!(server.handle("GET", "/shutdown", &[]).status, 404);
+        assert!(!server.stopping.load(Ordering::Acquire));
+    }
+
+    #[test]
+    fn test_parse_query() {
+        assert_eq!(
+            parse_query("from=ja&to=ko&text=%E3%81%AF%E3%81%84+%3Cb%3E%").unwrap(),
+            [
+                ("from".to_string(), "ja".to_string()),
+                ("to".to_string(), "ko".to_string()),
+                ("text".to_string(), "はい <b>%".to_string()),
+            ]
+        );
+        assert!(parse_query("text=%FF").is_none());
+    }
+
+    #[test]
+    fn test_xunity() {
+        let server = server(ServerConfig::default());
+        let reply = server.handle(
+            "GET",
+            "/xunity?from=ja&to=ko&text=%3Ccolor%3D%23FF0000%3E%E3%81%AF%E3%81%84%3C%2Fcolor%3E%0A%EC%84%B8%EA%B3%84%40%E3%81%84%E3%81%84%E3%81%88",
+            &[],
+        );
+        assert_eq!(reply.status, 200);
+        assert_eq!(reply.content_type, Reply::TEXT);
+        assert_eq!(reply.body, "<color=#FF0000>예</color>\n세계@아니오");
+
+        let reply = server.handle("GET", "/xunity?from=en&to=ko&text=hi", &[]);
+        assert_eq!(reply.status, 400);
+        assert_eq!(reply.content_type, Reply::TEXT);
+        assert_eq!(
+            server.handle("GET", "/xunity?from=ja&to=ko", &[]).status,
+            400
+        );
+    }
+
+    #[test]
+    fn test_http_round_trip() {
+        let server = Arc::new(server(ServerConfig {
+            max_body_bytes: 64,
+            workers: 2,
+            ..Default::default()
+        }));
+        let addr = server.local_addr().unwrap();
+        let running = {
+            let server = server.clone();
+            thread::spawn(move || server.run())
+        };
+
+        let send = |request: String| {
+            let mut stream = TcpStream::connect(addr).unwrap();
+            stream.write_all(request.as_bytes()).unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let post = |body: &str| {
+            send(format!(
+                "POST /translate HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\nContent-Length: {}\r\n\r\n{}",
+                body.len(),
+                body
+            ))
+        };
+
+        let response = post(r#"{"text":"ありがとう"}"#);
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("application/json"));
+        assert!(response.ends_with(r#"{"text":"고마워"}"#));
+
+        let response = post(&format!(r#"{{"text":"{}"}}"#, "あ".repeat(30)));
+        assert!(response.starts_with("HTTP/1.1 413"));
+
+        let response = send(
+            "GET /health HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n".to_string(),
+        );
+        assert!(response.ends_with(r#"{"status":"ok"}"#));
+
+        server.stop();
+        running.join().unwrap();
+    }
+}
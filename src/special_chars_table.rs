@@ -0,0 +1,140 @@
+// @generated by `cargo run --no-default-features --bin gen_special_chars`. Do not edit.
+//
+// EUC-KR로는 표현되지만 Shift-JIS로는 표현되지 않는 문자 (한글 제외, 1048자).
+// `J2K_TranslateMMNT`의 Shift-JIS 입력 -> EUC-KR 출력 경로를 그대로 통과할 수 없습니다.
+
+use crate::codec::BmpSet;
+
+pub(crate) static EUC_KR_ONLY: BmpSet = BmpSet::from_chars(&[
+    '\u{00A1}', '\u{00A4}', '\u{00AA}', '\u{00AD}', '\u{00AE}', '\u{00B2}', '\u{00B3}', '\u{00B7}',
+    '\u{00B8}', '\u{00B9}', '\u{00BA}', '\u{00BC}', '\u{00BD}', '\u{00BE}', '\u{00BF}', '\u{00C6}',
+    '\u{00D0}', '\u{00D8}', '\u{00DE}', '\u{00DF}', '\u{00E6}', '\u{00F0}', '\u{00F8}', '\u{00FE}',
+    '\u{0111}', '\u{0126}', '\u{0127}', '\u{0131}', '\u{0132}', '\u{0133}', '\u{0138}', '\u{013F}',
+    '\u{0140}', '\u{0141}', '\u{0142}', '\u{0149}', '\u{014A}', '\u{014B}', '\u{0152}', '\u{0153}',
+    '\u{0166}', '\u{0167}', '\u{02C7}', '\u{02D0}', '\u{02D8}', '\u{02D9}', '\u{02DA}', '\u{02DB}',
+    '\u{02DD}', '\u{2074}', '\u{207F}', '\u{2081}', '\u{2082}', '\u{2083}', '\u{2084}', '\u{20AC}',
+    '\u{2109}', '\u{2113}', '\u{2122}', '\u{2126}', '\u{2153}', '\u{2154}', '\u{215B}', '\u{215C}',
+    '\u{215D}', '\u{215E}', '\u{2194}', '\u{2195}', '\u{2196}', '\u{2197}', '\u{2198}', '\u{2199}',
+    '\u{220F}', '\u{223C}', '\u{2264}', '\u{2265}', '\u{2299}', '\u{2474}', '\u{2475}', '\u{2476}',
+    '\u{2477}', '\u{2478}', '\u{2479}', '\u{247A}', '\u{247B}', '\u{247C}', '\u{247D}', '\u{247E}',
+    '\u{247F}', '\u{2480}', '\u{2481}', '\u{2482}', '\u{249C}', '\u{249D}', '\u{249E}', '\u{249F}',
+    '\u{24A0}', '\u{24A1}', '\u{24A2}', '\u{24A3}', '\u{24A4}', '\u{24A5}', '\u{24A6}', '\u{24A7}',
+    '\u{24A8}', '\u{24A9}', '\u{24AA}', '\u{24AB}', '\u{24AC}', '\u{24AD}', '\u{24AE}', '\u{24AF}',
+    '\u{24B0}', '\u{24B1}', '\u{24B2}', '\u{24B3}', '\u{24B4}', '\u{24B5}', '\u{24D0}', '\u{24D1}',
+    '\u{24D2}', '\u{24D3}', '\u{24D4}', '\u{24D5}', '\u{24D6}', '\u{24D7}', '\u{24D8}', '\u{24D9}',
+    '\u{24DA}', '\u{24DB}', '\u{24DC}', '\u{24DD}', '\u{24DE}', '\u{24DF}', '\u{24E0}', '\u{24E1}',
+    '\u{24E2}', '\u{24E3}', '\u{24E4}', '\u{24E5}', '\u{24E6}', '\u{24E7}', '\u{24E8}', '\u{24E9}',
+    '\u{250D}', '\u{250E}', '\u{2511}', '\u{2512}', '\u{2515}', '\u{2516}', '\u{2519}', '\u{251A}',
+    '\u{251E}', '\u{251F}', '\u{2521}', '\u{2522}', '\u{2526}', '\u{2527}', '\u{2529}', '\u{252A}',
+    '\u{252D}', '\u{252E}', '\u{2531}', '\u{2532}', '\u{2535}', '\u{2536}', '\u{2539}', '\u{253A}',
+    '\u{253D}', '\u{253E}', '\u{2540}', '\u{2541}', '\u{2543}', '\u{2544}', '\u{2545}', '\u{2546}',
+    '\u{2547}', '\u{2548}', '\u{2549}', '\u{254A}', '\u{2592}', '\u{25A3}', '\u{25A4}', '\u{25A5}',
+    '\u{25A6}', '\u{25A7}', '\u{25A8}', '\u{25A9}', '\u{25B6}', '\u{25B7}', '\u{25C0}', '\u{25C1}',
+    '\u{25C8}', '\u{25D0}', '\u{25D1}', '\u{260E}', '\u{260F}', '\u{261C}', '\u{261E}', '\u{2660}',
+    '\u{2661}', '\u{2663}', '\u{2664}', '\u{2665}', '\u{2667}', '\u{2668}', '\u{2669}', '\u{266C}',
+    '\u{3200}', '\u{3201}', '\u{3202}', '\u{3203}', '\u{3204}', '\u{3205}', '\u{3206}', '\u{3207}',
+    '\u{3208}', '\u{3209}', '\u{320A}', '\u{320B}', '\u{320C}', '\u{320D}', '\u{320E}', '\u{320F}',
+    '\u{3210}', '\u{3211}', '\u{3212}', '\u{3213}', '\u{3214}', '\u{3215}', '\u{3216}', '\u{3217}',
+    '\u{3218}', '\u{3219}', '\u{321A}', '\u{321B}', '\u{321C}', '\u{3260}', '\u{3261}', '\u{3262}',
+    '\u{3263}', '\u{3264}', '\u{3265}', '\u{3266}', '\u{3267}', '\u{3268}', '\u{3269}', '\u{326A}',
+    '\u{326B}', '\u{326C}', '\u{326D}', '\u{326E}', '\u{326F}', '\u{3270}', '\u{3271}', '\u{3272}',
+    '\u{3273}', '\u{3274}', '\u{3275}', '\u{3276}', '\u{3277}', '\u{3278}', '\u{3279}', '\u{327A}',
+    '\u{327B}', '\u{327F}', '\u{3380}', '\u{3381}', '\u{3382}', '\u{3383}', '\u{3384}', '\u{3388}',
+    '\u{3389}', '\u{338A}', '\u{338B}', '\u{338C}', '\u{338D}', '\u{3390}', '\u{3391}', '\u{3392}',
+    '\u{3393}', '\u{3394}', '\u{3395}', '\u{3396}', '\u{3397}', '\u{3398}', '\u{3399}', '\u{339A}',
+    '\u{339B}', '\u{339F}', '\u{33A0}', '\u{33A2}', '\u{33A3}', '\u{33A4}', '\u{33A5}', '\u{33A6}',
+    '\u{33A7}', '\u{33A8}', '\u{33A9}', '\u{33AA}', '\u{33AB}', '\u{33AC}', '\u{33AD}', '\u{33AE}',
+    '\u{33AF}', '\u{33B0}', '\u{33B1}', '\u{33B2}', '\u{33B3}', '\u{33B4}', '\u{33B5}', '\u{33B6}',
+    '\u{33B7}', '\u{33B8}', '\u{33B9}', '\u{33BA}', '\u{33BB}', '\u{33BC}', '\u{33BD}', '\u{33BE}',
+    '\u{33BF}', '\u{33C0}', '\u{33C1}', '\u{33C2}', '\u{33C3}', '\u{33C5}', '\u{33C6}', '\u{33C7}',
+    '\u{33C8}', '\u{33C9}', '\u{33CA}', '\u{33CF}', '\u{33D0}', '\u{33D3}', '\u{33D6}', '\u{33D8}',
+    '\u{33DB}', '\u{33DC}', '\u{33DD}', '\u{4E6B}', '\u{4E6D}', '\u{4E76}', '\u{4E77}', '\u{4E90}',
+    '\u{4F0B}', '\u{4F48}', '\u{4F7A}', '\u{4F7E}', '\u{4F81}', '\u{4F84}', '\u{4FD3}', '\u{4FE0}',
+    '\u{4FF1}', '\u{5002}', '\u{501C}', '\u{5027}', '\u{503B}', '\u{50FF}', '\u{5106}', '\u{5167}',
+    '\u{51DE}', '\u{525D}', '\u{5261}', '\u{52A4}', '\u{52D6}', '\u{52FB}', '\u{5344}', '\u{5368}',
+    '\u{537D}', '\u{5433}', '\u{550E}', '\u{551C}', '\u{5535}', '\u{555E}', '\u{5653}', '\u{56AC}',
+    '\u{56CA}', '\u{56CD}', '\u{574D}', '\u{576E}', '\u{5770}', '\u{5775}', '\u{577C}', '\u{57F0}',
+    '\u{5808}', '\u{5809}', '\u{581E}', '\u{5827}', '\u{584F}', '\u{5861}', '\u{5864}', '\u{587C}',
+    '\u{5889}', '\u{58A9}', '\u{58CE}', '\u{5914}', '\u{596B}', '\u{596D}', '\u{5997}', '\u{59C3}',
+    '\u{59DD}', '\u{59EC}', '\u{59EE}', '\u{59F8}', '\u{5A1B}', '\u{5AA4}', '\u{5AC4}', '\u{5B05}',
+    '\u{5B7C}', '\u{5B93}', '\u{5B96}', '\u{5BAC}', '\u{5BD7}', '\u{5BEF}', '\u{5C19}', '\u{5C5B}',
+    '\u{5C62}', '\u{5CB5}', '\u{5CC0}', '\u{5CF4}', '\u{5D0D}', '\u{5DA0}', '\u{5DAA}', '\u{5DE2}',
+    '\u{5E3F}', '\u{5E77}', '\u{5EBE}', '\u{5F3A}', '\u{5F5B}', '\u{5FB5}', '\u{60A4}', '\u{60B0}',
+    '\u{6164}', '\u{617D}', '\u{6181}', '\u{6207}', '\u{6231}', '\u{6236}', '\u{623E}', '\u{62D6}',
+    '\u{633B}', '\u{637F}', '\u{63ED}', '\u{63F7}', '\u{6414}', '\u{6422}', '\u{6479}', '\u{6491}',
+    '\u{64C4}', '\u{64CA}', '\u{64E5}', '\u{6504}', '\u{656D}', '\u{657E}', '\u{6585}', '\u{65E3}',
+    '\u{65F4}', '\u{65FC}', '\u{65FD}', '\u{65FF}', '\u{6610}', '\u{6611}', '\u{6630}', '\u{663A}',
+    '\u{665B}', '\u{666B}', '\u{6677}', '\u{6678}', '\u{6690}', '\u{66B3}', '\u{66BB}', '\u{66C6}',
+    '\u{677B}', '\u{678F}', '\u{6793}', '\u{67B0}', '\u{67BE}', '\u{67D2}', '\u{67F0}', '\u{67F6}',
+    '\u{6812}', '\u{682F}', '\u{686D}', '\u{68A1}', '\u{68C5}', '\u{68E8}', '\u{6927}', '\u{6957}',
+    '\u{6963}', '\u{69A5}', '\u{69EA}', '\u{6A11}', '\u{6A52}', '\u{6A53}', '\u{6A89}', '\u{6AF6}',
+    '\u{6B0C}', '\u{6B46}', '\u{6B65}', '\u{6B72}', '\u{6B77}', '\u{6BAE}', '\u{6BC1}', '\u{6BCF}',
+    '\u{6BD7}', '\u{6C6D}', '\u{6C76}', '\u{6C85}', '\u{6C87}', '\u{6C94}', '\u{6C95}', '\u{6CC2}',
+    '\u{6CEB}', '\u{6CEE}', '\u{6D11}', '\u{6D27}', '\u{6D39}', '\u{6D7F}', '\u{6D89}', '\u{6D8D}',
+    '\u{6D91}', '\u{6DC3}', '\u{6DC4}', '\u{6DDA}', '\u{6E17}', '\u{6E32}', '\u{6E34}', '\u{6E36}',
+    '\u{6E3D}', '\u{6E44}', '\u{6E5E}', '\u{6E73}', '\u{6E7A}', '\u{6EAB}', '\u{6EB1}', '\u{6ECE}',
+    '\u{6F51}', '\u{6F57}', '\u{6F5A}', '\u{6F5E}', '\u{6F62}', '\u{6F7D}', '\u{6F7E}', '\u{6F8D}',
+    '\u{6F90}', '\u{6F94}', '\u{6FA7}', '\u{6FAE}', '\u{6FAF}', '\u{6FBE}', '\u{6FCA}', '\u{6FDA}',
+    '\u{6FE9}', '\u{7006}', '\u{701C}', '\u{7023}', '\u{702F}', '\u{7037}', '\u{7050}', '\u{705D}',
+    '\u{7098}', '\u{709A}', '\u{70A1}', '\u{70A4}', '\u{70B7}', '\u{70D8}', '\u{710C}', '\u{711E}',
+    '\u{7130}', '\u{714A}', '\u{7150}', '\u{715E}', '\u{7189}', '\u{7192}', '\u{71A2}', '\u{71BA}',
+    '\u{7200}', '\u{72C0}', '\u{7350}', '\u{7352}', '\u{7386}', '\u{738E}', '\u{7394}', '\u{7397}',
+    '\u{7398}', '\u{739F}', '\u{73A7}', '\u{73AD}', '\u{73B9}', '\u{73CC}', '\u{73CF}', '\u{73D9}',
+    '\u{73DD}', '\u{73E4}', '\u{73E6}', '\u{73E9}', '\u{73F7}', '\u{73F9}', '\u{73FD}', '\u{7401}',
+    '\u{7413}', '\u{741B}', '\u{7420}', '\u{7421}', '\u{7428}', '\u{742B}', '\u{742C}', '\u{742F}',
+    '\u{7430}', '\u{7438}', '\u{7440}', '\u{7443}', '\u{7444}', '\u{744B}', '\u{7457}', '\u{7465}',
+    '\u{7468}', '\u{7482}', '\u{7487}', '\u{7498}', '\u{749C}', '\u{74A1}', '\u{74A3}', '\u{74A5}',
+    '\u{74A8}', '\u{74AA}', '\u{74B2}', '\u{74B5}', '\u{74B9}', '\u{74BF}', '\u{74C6}', '\u{74D8}',
+    '\u{74DA}', '\u{751B}', '\u{7547}', '\u{7553}', '\u{7575}', '\u{757A}', '\u{7600}', '\u{7619}',
+    '\u{7626}', '\u{764E}', '\u{7664}', '\u{76FC}', '\u{774D}', '\u{782C}', '\u{784F}', '\u{78BB}',
+    '\u{78CE}', '\u{78F5}', '\u{78FB}', '\u{7916}', '\u{795B}', '\u{795C}', '\u{7991}', '\u{79B1}',
+    '\u{79CA}', '\u{7A05}', '\u{7A36}', '\u{7AD7}', '\u{7B2D}', '\u{7B60}', '\u{7B7D}', '\u{7BD2}',
+    '\u{7C1E}', '\u{7D07}', '\u{7D08}', '\u{7D6A}', '\u{7D7F}', '\u{7D8E}', '\u{7DE3}', '\u{7E15}',
+    '\u{7E1D}', '\u{7E2F}', '\u{7E47}', '\u{7E61}', '\u{7E6B}', '\u{7E98}', '\u{7FCE}', '\u{7FDF}',
+    '\u{8009}', '\u{802D}', '\u{8043}', '\u{8118}', '\u{812B}', '\u{8221}', '\u{8245}', '\u{828E}',
+    '\u{829A}', '\u{82A9}', '\u{82AE}', '\u{82B7}', '\u{82BC}', '\u{82BF}', '\u{82D5}', '\u{82FD}',
+    '\u{82FE}', '\u{8347}', '\u{8351}', '\u{8398}', '\u{83A9}', '\u{83C9}', '\u{83F9}', '\u{840A}',
+    '\u{84C0}', '\u{84C2}', '\u{851E}', '\u{8523}', '\u{8525}', '\u{852F}', '\u{853F}', '\u{858F}',
+    '\u{85CE}', '\u{85FF}', '\u{865B}', '\u{87EC}', '\u{881F}', '\u{8918}', '\u{8919}', '\u{8A75}',
+    '\u{8AAA}', '\u{8AEA}', '\u{8DC6}', '\u{8EC0}', '\u{8F1E}', '\u{8F27}', '\u{8F5D}', '\u{8FA6}',
+    '\u{8FF2}', '\u{9002}', '\u{9008}', '\u{905D}', '\u{9088}', '\u{9095}', '\u{9099}', '\u{90A2}',
+    '\u{90B0}', '\u{91AC}', '\u{91AE}', '\u{91B1}', '\u{91EA}', '\u{9212}', '\u{9217}', '\u{9265}',
+    '\u{92B3}', '\u{92B6}', '\u{92CC}', '\u{9304}', '\u{931F}', '\u{9324}', '\u{934A}', '\u{93A3}',
+    '\u{93F6}', '\u{9404}', '\u{9425}', '\u{95B1}', '\u{96E9}', '\u{96EF}', '\u{9777}', '\u{9800}',
+    '\u{980A}', '\u{9830}', '\u{9839}', '\u{985A}', '\u{98C7}', '\u{98E1}', '\u{994D}', '\u{9954}',
+    '\u{999D}', '\u{99B1}', '\u{99B9}', '\u{99D9}', '\u{9A08}', '\u{9A36}', '\u{9AE5}', '\u{9C49}',
+    '\u{9DD7}', '\u{9EB4}', '\u{9EB5}', '\u{9EC3}', '\u{9F90}', '\u{F900}', '\u{F901}', '\u{F902}',
+    '\u{F903}', '\u{F904}', '\u{F905}', '\u{F906}', '\u{F907}', '\u{F908}', '\u{F909}', '\u{F90A}',
+    '\u{F90B}', '\u{F90C}', '\u{F90D}', '\u{F90E}', '\u{F90F}', '\u{F910}', '\u{F911}', '\u{F912}',
+    '\u{F913}', '\u{F914}', '\u{F915}', '\u{F916}', '\u{F917}', '\u{F918}', '\u{F919}', '\u{F91A}',
+    '\u{F91B}', '\u{F91C}', '\u{F91D}', '\u{F91E}', '\u{F91F}', '\u{F920}', '\u{F921}', '\u{F922}',
+    '\u{F923}', '\u{F924}', '\u{F925}', '\u{F926}', '\u{F927}', '\u{F928}', '\u{F92A}', '\u{F92B}',
+    '\u{F92C}', '\u{F92D}', '\u{F92E}', '\u{F92F}', '\u{F930}', '\u{F931}', '\u{F932}', '\u{F933}',
+    '\u{F934}', '\u{F935}', '\u{F936}', '\u{F937}', '\u{F938}', '\u{F939}', '\u{F93A}', '\u{F93B}',
+    '\u{F93C}', '\u{F93D}', '\u{F93E}', '\u{F93F}', '\u{F940}', '\u{F941}', '\u{F942}', '\u{F943}',
+    '\u{F944}', '\u{F945}', '\u{F946}', '\u{F947}', '\u{F948}', '\u{F949}', '\u{F94A}', '\u{F94B}',
+    '\u{F94C}', '\u{F94D}', '\u{F94E}', '\u{F94F}', '\u{F950}', '\u{F951}', '\u{F952}', '\u{F953}',
+    '\u{F954}', '\u{F955}', '\u{F956}', '\u{F957}', '\u{F958}', '\u{F959}', '\u{F95A}', '\u{F95B}',
+    '\u{F95C}', '\u{F95D}', '\u{F95E}', '\u{F95F}', '\u{F960}', '\u{F961}', '\u{F962}', '\u{F963}',
+    '\u{F964}', '\u{F965}', '\u{F966}', '\u{F967}', '\u{F968}', '\u{F969}', '\u{F96A}', '\u{F96B}',
+    '\u{F96C}', '\u{F96D}', '\u{F96E}', '\u{F96F}', '\u{F970}', '\u{F971}', '\u{F972}', '\u{F973}',
+    '\u{F974}', '\u{F975}', '\u{F976}', '\u{F977}', '\u{F978}', '\u{F979}', '\u{F97A}', '\u{F97B}',
+    '\u{F97C}', '\u{F97D}', '\u{F97E}', '\u{F97F}', '\u{F980}', '\u{F981}', '\u{F982}', '\u{F983}',
+    '\u{F984}', '\u{F985}', '\u{F986}', '\u{F987}', '\u{F988}', '\u{F989}', '\u{F98A}', '\u{F98B}',
+    '\u{F98C}', '\u{F98D}', '\u{F98E}', '\u{F98F}', '\u{F990}', '\u{F991}', '\u{F992}', '\u{F993}',
+    '\u{F994}', '\u{F995}', '\u{F996}', '\u{F997}', '\u{F998}', '\u{F999}', '\u{F99A}', '\u{F99B}',
+    '\u{F99C}', '\u{F99D}', '\u{F99E}', '\u{F99F}', '\u{F9A0}', '\u{F9A1}', '\u{F9A2}', '\u{F9A3}',
+    '\u{F9A4}', '\u{F9A5}', '\u{F9A6}', '\u{F9A7}', '\u{F9A8}', '\u{F9A9}', '\u{F9AA}', '\u{F9AB}',
+    '\u{F9AC}', '\u{F9AD}', '\u{F9AE}', '\u{F9AF}', '\u{F9B0}', '\u{F9B1}', '\u{F9B2}', '\u{F9B3}',
+    '\u{F9B4}', '\u{F9B5}', '\u{F9B6}', '\u{F9B7}', '\u{F9B8}', '\u{F9B9}', '\u{F9BA}', '\u{F9BB}',
+    '\u{F9BC}', '\u{F9BD}', '\u{F9BE}', '\u{F9BF}', '\u{F9C0}', '\u{F9C1}', '\u{F9C2}', '\u{F9C3}',
+    '\u{F9C4}', '\u{F9C5}', '\u{F9C6}', '\u{F9C7}', '\u{F9C8}', '\u{F9C9}', '\u{F9CA}', '\u{F9CB}',
+    '\u{F9CC}', '\u{F9CD}', '\u{F9CE}', '\u{F9CF}', '\u{F9D0}', '\u{F9D1}', '\u{F9D2}', '\u{F9D3}',
+    '\u{F9D4}', '\u{F9D5}', '\u{F9D6}', '\u{F9D7}', '\u{F9D8}', '\u{F9D9}', '\u{F9DA}', '\u{F9DB}',
+    '\u{F9DD}', '\u{F9DE}', '\u{F9DF}', '\u{F9E0}', '\u{F9E1}', '\u{F9E2}', '\u{F9E3}', '\u{F9E4}',
+    '\u{F9E5}', '\u{F9E6}', '\u{F9E7}', '\u{F9E8}', '\u{F9E9}', '\u{F9EA}', '\u{F9EB}', '\u{F9EC}',
+    '\u{F9ED}', '\u{F9EE}', '\u{F9EF}', '\u{F9F0}', '\u{F9F1}', '\u{F9F2}', '\u{F9F3}', '\u{F9F4}',
+    '\u{F9F5}', '\u{F9F6}', '\u{F9F7}', '\u{F9F8}', '\u{F9F9}', '\u{F9FA}', '\u{F9FB}', '\u{F9FC}',
+    '\u{F9FD}', '\u{F9FE}', '\u{F9FF}', '\u{FA00}', '\u{FA01}', '\u{FA02}', '\u{FA03}', '\u{FA04}',
+    '\u{FA05}', '\u{FA06}', '\u{FA07}', '\u{FA08}', '\u{FA09}', '\u{FA0A}', '\u{FA0B}', '\u{FFE6}',
+]);
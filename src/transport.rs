@@ -0,0 +1,433 @@
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use crate::EzTransError;
+
+/// 번역 호스트가 메시지를 주고받는 통로
+///
+/// 메시지 하나는 번역할 문장 하나, 또는 [`protocol`](crate::protocol)의 JSON 요청 하나입니다.
+pub trait Transport {
+    /// 다음 메시지를 기다립니다. 상대가 끝내서 더 받을 메시지가 없으면 `None`을 반환합니다.
+    fn recv(&mut self) -> Result<Option<String>, EzTransError>;
+
+    /// 마지막으로 받은 메시지의 상대에게 메시지를 보냅니다.
+    fn send(&mut self, message: &str) -> Result<(), EzTransError>;
+}
+
+impl<T: Transport + ?Sized> Transport for Box<T> {
+    fn recv(&mut self) -> Result<Option<String>, EzTransError> {
+        (**self).recv()
+    }
+
+    fn send(&mut self, message: &str) -> Result<(), EzTransError> {
+        (**self).send(message)
+    }
+}
+
+fn io_error(error: io::Error) -> EzTransError {
+    EzTransError::TransportError(error.to_string())
+}
+
+/// 한 줄에 메시지 하나를 주고받는 통로 (UTF-8, `\n` 구분)
+///
+/// 받은 줄 끝의 `\r\n`이나 `\n`은 지웁니다. 줄바꿈이 들어 있는 메시지는 보낼 수 없습니다.
+#[derive(Debug)]
+pub struct LineTransport<R, W> {
+    reader: R,
+    writer: W,
+}
+
+impl<R: BufRead, W: Write> LineTransport<R, W> {
+    pub fn new(reader: R, writer: W) -> Self {
+        LineTransport { reader, writer }
+    }
+
+    pub fn into_inner(self) -> (R, W) {
+        (self.reader, self.writer)
+    }
+}
+
+/// 표준 입력으로 받고 표준 출력으로 보내는 통로
+pub type StdioTransport = LineTransport<io::StdinLock<'static>, io::Stdout>;
+
+impl StdioTransport {
+    pub fn stdio() -> Self {
+        LineTransport::new(io::stdin().lock(), io::stdout())
+    }
+}
+
+impl<R: BufRead, W: Write> Transport for LineTransport<R, W> {
+    fn recv(&mut self) -> Result<Option<String>, EzTransError> {
+        let mut line = String::new();
+        if self.reader.read_line(&mut line).map_err(io_error)? == 0 {
+            return Ok(None);
+        }
+        if line.ends_with('\n') {
+            line.pop();
+            if line.ends_with('\r') {
+                line.pop();
+            }
+        }
+        Ok(Some(line))
+    }
+
+    fn send(&mut self, message: &str) -> Result<(), EzTransError> {
+        if message.contains('\n') {
+            return Err(EzTransError::TransportError(
+                "message contains a line break".to_string(),
+            ));
+        }
+        self.writer
+            .write_all(message.as_bytes())
+            .and_then(|()| self.writer.write_all(b"\n"))
+            .and_then(|()| self.writer.flush())
+            .map_err(io_error)
+    }
+}
+
+/// [`ListenerTransport`]가 연결을 받는 소켓
+pub trait Listener {
+    type Stream: io::Read + Write;
+
+    fn accept_stream(&self) -> io::Result<Self::Stream>;
+    fn try_clone_stream(stream: &Self::Stream) -> io::Result<Self::Stream>;
+    /// 받은 연결의 읽기/쓰기 제한 시간을 정합니다. `None`이면 제한하지 않습니다.
+    fn set_stream_timeout(stream: &Self::Stream, timeout: Option<Duration>) -> io::Result<()>;
+}
+
+impl Listener for TcpListener {
+    type Stream = TcpStream;
+
+    fn accept_stream(&self) -> io::Result<TcpStream> {
+        self.accept().map(|(stream, _)| stream)
+    }
+
+    fn try_clone_stream(stream: &TcpStream) -> io::Result<TcpStream> {
+        stream.try_clone()
+    }
+
+    fn set_stream_timeout(stream: &TcpStream, timeout: Option<Duration>) -> io::Result<()> {
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)
+    }
+}
+
+#[cfg(unix)]
+impl Listener for UnixListener {
+    type Stream = UnixStream;
+
+    fn accept_stream(&self) -> io::Result<UnixStream> {
+        self.accept().map(|(stream, _)| stream)
+    }
+
+    fn try_clone_stream(stream: &UnixStream) -> io::Result<UnixStream> {
+        stream.try_clone()
+    }
+
+    fn set_stream_timeout(stream: &UnixStream, timeout: Option<Duration>) -> io::Result<()> {
+        stream.set_read_timeout(timeout)?;
+        stream.set_write_timeout(timeout)
+    }
+}
+
+/// [`ListenerTransport`]가 연결에서 메시지를 기다리는 기본 시간
+pub const DEFAULT_IDLE_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// 소켓에서 연결을 하나씩 받아 [`LineTransport`]로 주고받는 통로
+///
+/// 엔진은 한 번에 한 문장만 번역하므로 연결도 한 번에 하나만 처리합니다.
+/// 연결을 받지 못하거나, 연결이 끊기거나 답을 보내지 못하면 그 연결을 버리고 다음 연결을 기다리므로, `recv`가 `None`을 반환하지 않습니다.
+/// 연결한 뒤 보내지 않는 클라이언트가 다른 클라이언트를 막지 않도록, 제한 시간([`DEFAULT_IDLE_TIMEOUT`]) 동안
+/// 메시지가 오지 않는 연결도 버립니다. 연결을 계속 열어 두는 클라이언트는 그 안에 다음 메시지를 보내거나 다시 연결해야 합니다.
+pub struct ListenerTransport<L: Listener> {
+    listener: L,
+    idle_timeout: Option<Duration>,
+    connection: Option<LineTransport<BufReader<L::Stream>, L::Stream>>,
+}
+
+/// TCP 소켓으로 주고받는 통로
+pub type TcpTransport = ListenerTransport<TcpListener>;
+/// Unix 도메인 소켓으로 주고받는 통로
+#[cfg(unix)]
+pub type UnixTransport = ListenerTransport<UnixListener>;
+
+impl<L: Listener> ListenerTransport<L> {
+    pub fn new(listener: L) -> Self {
+        ListenerTransport {
+            listener,
+            idle_timeout: Some(DEFAULT_IDLE_TIMEOUT),
+            connection: None,
+        }
+    }
+
+    /// 연결에서 메시지를 기다리는 시간을 바꿉니다. `None`이면 한 클라이언트가 끊을 때까지 기다립니다.
+    pub fn with_idle_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.idle_timeout = timeout;
+        self
+    }
+
+    pub fn listener(&self) -> &L {
+        &self.listener
+    }
+}
+
+impl TcpTransport {
+    /// `addr`(`127.0.0.1:7070` 등)에서 연결을 기다립니다.
+    pub fn bind(addr: &str) -> Result<Self, EzTransError> {
+        let listener = TcpListener::bind(addr)
+            .map_err(|e| EzTransError::TransportError(format!("{}: {}", addr, e)))?;
+        Ok(ListenerTransport::new(listener))
+    }
+}
+
+#[cfg(unix)]
+impl UnixTransport {
+    /// `path`에 소켓 파일을 만들고 연결을 기다립니다. 이미 있는 파일은 지우지 않습니다.
+    pub fn bind(path: impl AsRef<std::path::Path>) -> Result<Self, EzTransError> {
+        let path = path.as_ref();
+        let listener = UnixListener::bind(path)
+            .map_err(|e| EzTransError::TransportError(format!("{}: {}", path.display(), e)))?;
+        Ok(ListenerTransport::new(listener))
+    }
+}
+
+impl<L: Listener> Transport for ListenerTransport<L> {
+    fn recv(&mut self) -> Result<Option<String>, EzTransError> {
+        loop {
+            let connection = match &mut self.connection {
+                Some(connection) => connection,
+                None => {
+                    // 연결 하나를 받지 못해도 호스트를 끝내지 않고 다음 연결을 기다립니다.
+                    let stream = match self.listener.accept_stream() {
+                        Ok(stream) => stream,
+                        Err(e) => {
+                            eprintln!("Failed to accept a connection: {}", e);
+                            continue;
+                        }
+                    };
+                    if let Err(e) = L::set_stream_timeout(&stream, self.idle_timeout) {
+                        eprintln!("Failed to set the connection timeout: {}", e);
+                        continue;
+                    }
+                    let reader = match L::try_clone_stream(&stream) {
+                        Ok(reader) => BufReader::new(reader),
+                        Err(e) => {
+                            eprintln!("Failed to clone the connection: {}", e);
+                            continue;
+                        }
+                    };
+                    self.connection.insert(LineTransport::new(reader, stream))
+                }
+            };
+            match connection.recv() {
+                Ok(Some(message)) => return Ok(Some(message)),
+                // 상대가 연결을 끊었거나 제한 시간 안에 보내지 않았으면 다음 연결을 기다립니다.
+                Ok(None) | Err(_) => self.connection = None,
+            }
+        }
+    }
+
+    fn send(&mut self, message: &str) -> Result<(), EzTransError> {
+        let connection = self
+            .connection
+            .as_mut()
+            .ok_or_else(|| EzTransError::TransportError("no client is connected".to_string()))?;
+        if connection.send(message).is_err() {
+            // 한 클라이언트가 먼저 끊어도 호스트는 계속 다음 연결을 받습니다.
+            self.connection = None;
+        }
+        Ok(())
+    }
+}
+
+/// `windows_shared_memory`의 클라이언트로 주고받는 통로
+#[cfg(feature = "native-engine")]
+pub struct SharedMemoryTransport {
+    client: windows_shared_memory::Client,
+}
+
+#[cfg(feature = "native-engine")]
+impl SharedMemoryTransport {
+    /// `name`이 `None`이면 기본 이름의 공유 메모리를 사용합니다.
+    pub fn new(name: Option<&str>) -> Result<Self, EzTransError> {
+        let client = windows_shared_memory::Client::new(name)
+            .map_err(|e| EzTransError::SharedMemoryError(e.to_string()))?;
+        Ok(SharedMemoryTransport { client })
+    }
+}
+
+#[cfg(feature = "native-engine")]
+impl Transport for SharedMemoryTransport {
+    fn recv(&mut self) -> Result<Option<String>, EzTransError> {
+        use windows_shared_memory::RecieveMessage;
+
+        match self.client.recv_s2c(true) {
+            RecieveMessage::Message(message) => Ok(Some(message)),
+            // 서버가 끝났거나 공유 메모리를 읽지 못하면 그대로 종료합니다.
+            RecieveMessage::Exit | RecieveMessage::Error => Ok(None),
+        }
+    }
+
+    fn send(&mut self, message: &str) -> Result<(), EzTransError> {
+        self.client
+            .send_c2s(message.as_bytes())
+            .map_err(|e| EzTransError::SharedMemoryError(e.to_string()))
+    }
+}
+
+/// 명령줄에서 고르는 통로 (`shm`, `stdio`, `tcp:ADDR`, `unix:PATH`)
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum Endpoint {
+    #[default]
+    SharedMemory,
+    Stdio,
+    Tcp(String),
+    Unix(PathBuf),
+}
+
+impl FromStr for Endpoint {
+    type Err = EzTransError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "shm" => Ok(Endpoint::SharedMemory),
+            "stdio" => Ok(Endpoint::Stdio),
+            _ => {
+                if let Some(addr) = s.strip_prefix("tcp:") {
+                    Ok(Endpoint::Tcp(addr.to_string()))
+                } else if let Some(path) = s.strip_prefix("unix:") {
+                    Ok(Endpoint::Unix(PathBuf::from(path)))
+                } else {
+                    Err(EzTransError::ConfigError(format!(
+                        "unknown transport `{}` (expected shm, stdio, tcp:ADDR or unix:PATH)",
+                        s
+                    )))
+                }
+            }
+        }
+    }
+}
+
+impl Endpoint {
+    /// 이 통로를 엽니다. 소켓이면 연결을 기다릴 준비까지 합니다.
+    pub fn open(&self) -> Result<Box<dyn Transport>, EzTransError> {
+        match self {
+            #[cfg(feature = "native-engine")]
+            Endpoint::SharedMemory => Ok(Box::new(SharedMemoryTransport::new(None)?)),
+            #[cfg(not(feature = "native-engine"))]
+            Endpoint::SharedMemory => Err(EzTransError::FeatureDisabled("native-engine")),
+            Endpoint::Stdio => Ok(Box::new(StdioTransport::stdio())),
+            Endpoint::Tcp(addr) => Ok(Box::new(TcpTransport::bind(addr)?)),
+            #[cfg(unix)]
+            Endpoint::Unix(path) => Ok(Box::new(UnixTransport::bind(path)?)),
+            #[cfg(not(unix))]
+            Endpoint::Unix(_) => Err(EzTransError::Unsupported(
+                "Unix domain sockets on this platform".to_string(),
+            )),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_line_transport() {
+        let input = Cursor::new("はい\r\nいいえ\n最後");
+        let mut transport = LineTransport::new(input, Vec::new());
+        assert_eq!(transport.recv().unwrap().as_deref(), Some("はい"));
+        assert_eq!(transport.recv().unwrap().as_deref(), Some("いいえ"));
+        assert_eq!(transport.recv().unwrap().as_deref(), Some("最後"));
+        assert_eq!(transport.recv().unwrap(), None);
+
+        transport.send("예").unwrap();
+        assert!(transport.send("두\n줄").is_err());
+        assert_eq!(transport.into_inner().1, "예\n".as_bytes());
+    }
+
+    #[test]
+    fn test_idle_client_does_not_block_others() {
+        use std::io::Read;
+
+        let mut transport = TcpTransport::bind("127.0.0.1:0")
+            .unwrap()
+            .with_idle_timeout(Some(Duration::from_millis(50)));
+        let addr = transport.listener().local_addr().unwrap();
+        let idle = TcpStream::connect(addr).unwrap();
+        let mut active = TcpStream::connect(addr).unwrap();
+        active.write_all("はい\n".as_bytes()).unwrap();
+
+        assert_eq!(transport.recv().unwrap().as_deref(), Some("はい"));
+        transport.send("예").unwrap();
+        let mut reply = [0; 4];
+        active.read_exact(&mut reply).unwrap();
+        assert_eq!(&reply, "예\n".as_bytes());
+
+        // 먼저 연결한 클라이언트는 제한 시간이 지나 끊겼습니다.
+        let mut idle = idle;
+        assert_eq!(idle.read(&mut [0; 1]).unwrap(), 0);
+    }
+
+    /// 처음 `failures`번은 연결을 받지 못하는 소켓
+    struct FlakyListener {
+        listener: TcpListener,
+        failures: std::cell::Cell<usize>,
+    }
+
+    impl Listener for FlakyListener {
+        type Stream = TcpStream;
+
+        fn accept_stream(&self) -> io::Result<TcpStream> {
+            if self.failures.get() > 0 {
+                self.failures.set(self.failures.get() - 1);
+                return Err(io::Error::other("too many open files"));
+            }
+            self.listener.accept_stream()
+        }
+
+        fn try_clone_stream(stream: &TcpStream) -> io::Result<TcpStream> {
+            TcpListener::try_clone_stream(stream)
+        }
+
+        fn set_stream_timeout(stream: &TcpStream, timeout: Option<Duration>) -> io::Result<()> {
+            TcpListener::set_stream_timeout(stream, timeout)
+        }
+    }
+
+    #[test]
+    fn test_failed_accept_waits_for_next_connection() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let mut transport = ListenerTransport::new(FlakyListener {
+            listener,
+            failures: std::cell::Cell::new(1),
+        });
+        let mut client = TcpStream::connect(addr).unwrap();
+        client.write_all("はい\n".as_bytes()).unwrap();
+
+        assert_eq!(transport.recv().unwrap().as_deref(), Some("はい"));
+        assert_eq!(transport.listener().failures.get(), 0);
+    }
+
+    #[test]
+    fn test_parse_endpoint() {
+        assert_eq!("shm".parse::<Endpoint>().unwrap(), Endpoint::SharedMemory);
+        assert_eq!("stdio".parse::<Endpoint>().unwrap(), Endpoint::Stdio);
+        assert_eq!(
+            "tcp:127.0.0.1:7070".parse::<Endpoint>().unwrap(),
+            Endpoint::Tcp("127.0.0.1:7070".to_string())
+        );
+        assert_eq!(
+            "unix:/tmp/eztrans.sock".parse::<Endpoint>().unwrap(),
+            Endpoint::Unix(PathBuf::from("/tmp/eztrans.sock"))
+        );
+        assert!("pipe".parse::<Endpoint>().is_err());
+    }
+}
@@ -0,0 +1,128 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, SystemTime};
+
+/// 사전 폴더를 주기적으로 살펴보고 파일이 바뀌었는지 알려 줍니다.
+///
+/// 엔진 핸들은 스레드 사이에서 옮길 수 없으므로 사전을 직접 다시 읽지 않습니다.
+/// 번역 루프에서 [`take_changed`](Self::take_changed)가 `true`를 돌려주면
+/// [`TranslationEngine::reload_user_dict`](crate::TranslationEngine::reload_user_dict)를 호출하세요.
+///
+/// 폴더 바로 아래 파일의 추가·삭제·크기·수정 시각만 비교합니다. drop되면 감시 스레드를 멈춥니다.
+pub struct DictWatcher {
+    dir: PathBuf,
+    changed: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+/// 파일 경로 → (크기, 수정 시각)
+type DirState = BTreeMap<PathBuf, (u64, Option<SystemTime>)>;
+
+impl DictWatcher {
+    pub const DEFAULT_INTERVAL: Duration = Duration::from_secs(1);
+
+    /// `dir`을 `interval`마다 살펴보는 감시 스레드를 시작합니다.
+    pub fn new(dir: impl Into<PathBuf>, interval: Duration) -> std::io::Result<Self> {
+        let dir = dir.into();
+        let mut state = read_state(&dir)?;
+        let changed = Arc::new(AtomicBool::new(false));
+        let stop = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let dir = dir.clone();
+            let changed = changed.clone();
+            let stop = stop.clone();
+            thread::Builder::new()
+                .name("eztrans-dict-watcher".to_string())
+                .spawn(move || {
+                    while !stop.load(Ordering::Relaxed) {
+                        thread::park_timeout(interval);
+                        // 폴더를 잠시 읽을 수 없으면 다음 주기에 다시 봅니다.
+                        if let Ok(current) = read_state(&dir) {
+                            if current != state {
+                                state = current;
+                                changed.store(true, Ordering::Release);
+                            }
+                        }
+                    }
+                })?
+        };
+
+        Ok(DictWatcher {
+            dir,
+            changed,
+            stop,
+            handle: Some(handle),
+        })
+    }
+
+    /// 감시하는 폴더
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 마지막 호출 이후 사전 파일이 바뀌었으면 `true`를 돌려주고 표시를 지웁니다.
+    pub fn take_changed(&self) -> bool {
+        self.changed.swap(false, Ordering::Acquire)
+    }
+}
+
+impl Drop for DictWatcher {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            handle.thread().unpark();
+            let _ = handle.join();
+        }
+    }
+}
+
+fn read_state(dir: &Path) -> std::io::Result<DirState> {
+    let mut state = DirState::new();
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if metadata.is_file() {
+            state.insert(entry.path(), (metadata.len(), metadata.modified().ok()));
+        }
+    }
+    Ok(state)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_new_and_modified_files() {
+        let dir = std::env::temp_dir().join(format!("eztrans-dict-watcher-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("UserDict.jk"), "a").unwrap();
+
+        let watcher = DictWatcher::new(&dir, Duration::from_millis(10)).unwrap();
+        thread::sleep(Duration::from_millis(50));
+        assert!(!watcher.take_changed());
+
+        fs::write(dir.join("UserDict.jk"), "ab").unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert!(watcher.take_changed());
+        assert!(!watcher.take_changed());
+
+        fs::write(dir.join("UserDict2.jk"), "a").unwrap();
+        thread::sleep(Duration::from_millis(100));
+        assert!(watcher.take_changed());
+
+        drop(watcher);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_dir_is_an_error() {
+        assert!(DictWatcher::new("./does-not-exist", DictWatcher::DEFAULT_INTERVAL).is_err());
+    }
+}